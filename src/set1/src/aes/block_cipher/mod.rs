@@ -0,0 +1,360 @@
+use super::{AesError, BLOCK_SIZE};
+
+pub type State = [u8; BLOCK_SIZE];
+
+static SBOX: [u8; 256] = [
+    0x63, 0x7c, 0x77, 0x7b, 0xf2, 0x6b, 0x6f, 0xc5, 0x30, 0x01, 0x67, 0x2b, 0xfe, 0xd7, 0xab, 0x76,
+    0xca, 0x82, 0xc9, 0x7d, 0xfa, 0x59, 0x47, 0xf0, 0xad, 0xd4, 0xa2, 0xaf, 0x9c, 0xa4, 0x72, 0xc0,
+    0xb7, 0xfd, 0x93, 0x26, 0x36, 0x3f, 0xf7, 0xcc, 0x34, 0xa5, 0xe5, 0xf1, 0x71, 0xd8, 0x31, 0x15,
+    0x04, 0xc7, 0x23, 0xc3, 0x18, 0x96, 0x05, 0x9a, 0x07, 0x12, 0x80, 0xe2, 0xeb, 0x27, 0xb2, 0x75,
+    0x09, 0x83, 0x2c, 0x1a, 0x1b, 0x6e, 0x5a, 0xa0, 0x52, 0x3b, 0xd6, 0xb3, 0x29, 0xe3, 0x2f, 0x84,
+    0x53, 0xd1, 0x00, 0xed, 0x20, 0xfc, 0xb1, 0x5b, 0x6a, 0xcb, 0xbe, 0x39, 0x4a, 0x4c, 0x58, 0xcf,
+    0xd0, 0xef, 0xaa, 0xfb, 0x43, 0x4d, 0x33, 0x85, 0x45, 0xf9, 0x02, 0x7f, 0x50, 0x3c, 0x9f, 0xa8,
+    0x51, 0xa3, 0x40, 0x8f, 0x92, 0x9d, 0x38, 0xf5, 0xbc, 0xb6, 0xda, 0x21, 0x10, 0xff, 0xf3, 0xd2,
+    0xcd, 0x0c, 0x13, 0xec, 0x5f, 0x97, 0x44, 0x17, 0xc4, 0xa7, 0x7e, 0x3d, 0x64, 0x5d, 0x19, 0x73,
+    0x60, 0x81, 0x4f, 0xdc, 0x22, 0x2a, 0x90, 0x88, 0x46, 0xee, 0xb8, 0x14, 0xde, 0x5e, 0x0b, 0xdb,
+    0xe0, 0x32, 0x3a, 0x0a, 0x49, 0x06, 0x24, 0x5c, 0xc2, 0xd3, 0xac, 0x62, 0x91, 0x95, 0xe4, 0x79,
+    0xe7, 0xc8, 0x37, 0x6d, 0x8d, 0xd5, 0x4e, 0xa9, 0x6c, 0x56, 0xf4, 0xea, 0x65, 0x7a, 0xae, 0x08,
+    0xba, 0x78, 0x25, 0x2e, 0x1c, 0xa6, 0xb4, 0xc6, 0xe8, 0xdd, 0x74, 0x1f, 0x4b, 0xbd, 0x8b, 0x8a,
+    0x70, 0x3e, 0xb5, 0x66, 0x48, 0x03, 0xf6, 0x0e, 0x61, 0x35, 0x57, 0xb9, 0x86, 0xc1, 0x1d, 0x9e,
+    0xe1, 0xf8, 0x98, 0x11, 0x69, 0xd9, 0x8e, 0x94, 0x9b, 0x1e, 0x87, 0xe9, 0xce, 0x55, 0x28, 0xdf,
+    0x8c, 0xa1, 0x89, 0x0d, 0xbf, 0xe6, 0x42, 0x68, 0x41, 0x99, 0x2d, 0x0f, 0xb0, 0x54, 0xbb, 0x16,
+];
+
+static INV_SBOX: [u8; 256] = [
+    0x52, 0x09, 0x6a, 0xd5, 0x30, 0x36, 0xa5, 0x38, 0xbf, 0x40, 0xa3, 0x9e, 0x81, 0xf3, 0xd7, 0xfb,
+    0x7c, 0xe3, 0x39, 0x82, 0x9b, 0x2f, 0xff, 0x87, 0x34, 0x8e, 0x43, 0x44, 0xc4, 0xde, 0xe9, 0xcb,
+    0x54, 0x7b, 0x94, 0x32, 0xa6, 0xc2, 0x23, 0x3d, 0xee, 0x4c, 0x95, 0x0b, 0x42, 0xfa, 0xc3, 0x4e,
+    0x08, 0x2e, 0xa1, 0x66, 0x28, 0xd9, 0x24, 0xb2, 0x76, 0x5b, 0xa2, 0x49, 0x6d, 0x8b, 0xd1, 0x25,
+    0x72, 0xf8, 0xf6, 0x64, 0x86, 0x68, 0x98, 0x16, 0xd4, 0xa4, 0x5c, 0xcc, 0x5d, 0x65, 0xb6, 0x92,
+    0x6c, 0x70, 0x48, 0x50, 0xfd, 0xed, 0xb9, 0xda, 0x5e, 0x15, 0x46, 0x57, 0xa7, 0x8d, 0x9d, 0x84,
+    0x90, 0xd8, 0xab, 0x00, 0x8c, 0xbc, 0xd3, 0x0a, 0xf7, 0xe4, 0x58, 0x05, 0xb8, 0xb3, 0x45, 0x06,
+    0xd0, 0x2c, 0x1e, 0x8f, 0xca, 0x3f, 0x0f, 0x02, 0xc1, 0xaf, 0xbd, 0x03, 0x01, 0x13, 0x8a, 0x6b,
+    0x3a, 0x91, 0x11, 0x41, 0x4f, 0x67, 0xdc, 0xea, 0x97, 0xf2, 0xcf, 0xce, 0xf0, 0xb4, 0xe6, 0x73,
+    0x96, 0xac, 0x74, 0x22, 0xe7, 0xad, 0x35, 0x85, 0xe2, 0xf9, 0x37, 0xe8, 0x1c, 0x75, 0xdf, 0x6e,
+    0x47, 0xf1, 0x1a, 0x71, 0x1d, 0x29, 0xc5, 0x89, 0x6f, 0xb7, 0x62, 0x0e, 0xaa, 0x18, 0xbe, 0x1b,
+    0xfc, 0x56, 0x3e, 0x4b, 0xc6, 0xd2, 0x79, 0x20, 0x9a, 0xdb, 0xc0, 0xfe, 0x78, 0xcd, 0x5a, 0xf4,
+    0x1f, 0xdd, 0xa8, 0x33, 0x88, 0x07, 0xc7, 0x31, 0xb1, 0x12, 0x10, 0x59, 0x27, 0x80, 0xec, 0x5f,
+    0x60, 0x51, 0x7f, 0xa9, 0x19, 0xb5, 0x4a, 0x0d, 0x2d, 0xe5, 0x7a, 0x9f, 0x93, 0xc9, 0x9c, 0xef,
+    0xa0, 0xe0, 0x3b, 0x4d, 0xae, 0x2a, 0xf5, 0xb0, 0xc8, 0xeb, 0xbb, 0x3c, 0x83, 0x53, 0x99, 0x61,
+    0x17, 0x2b, 0x04, 0x7e, 0xba, 0x77, 0xd6, 0x26, 0xe1, 0x69, 0x14, 0x63, 0x55, 0x21, 0x0c, 0x7d,
+];
+
+static RCON: [u8; 10] = [0x01, 0x02, 0x04, 0x08, 0x10, 0x20, 0x40, 0x80, 0x1b, 0x36];
+
+// AES-128/192/256 block cipher with the expanded round keys
+pub struct Aes {
+    round_keys: Vec<State>,
+}
+
+impl Aes {
+    pub fn new(key: &[u8]) -> Result<Self, AesError> {
+        Ok(Aes { round_keys: expand_key(key)? })
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.round_keys.len() - 1
+    }
+
+    pub fn round_keys(&self) -> &[State] {
+        &self.round_keys
+    }
+
+    pub fn encrypt_block(&self, block: &mut State) {
+        encrypt_rounds(block, &self.round_keys, |_, _| {});
+    }
+
+    pub fn decrypt_block(&self, block: &mut State) {
+        decrypt_rounds(block, &self.round_keys);
+    }
+}
+
+// runs one round per round key after the first, the last one without MixColumns
+// the observer is called with the state after the initial AddRoundKey (round 0) and after every round
+pub fn encrypt_rounds(block: &mut State, round_keys: &[State], mut observer: impl FnMut(usize, &State)) {
+    let rounds = round_keys.len() - 1;
+
+    add_round_key(block, &round_keys[0]);
+    observer(0, block);
+
+    for (round, round_key) in round_keys.iter().enumerate().skip(1) {
+        sub_bytes(block);
+        shift_rows(block);
+
+        if round < rounds {
+            mix_columns(block);
+        }
+
+        add_round_key(block, round_key);
+        observer(round, block);
+    }
+}
+
+pub fn decrypt_rounds(block: &mut State, round_keys: &[State]) {
+    let rounds = round_keys.len() - 1;
+
+    for (round, round_key) in round_keys.iter().enumerate().skip(1).rev() {
+        add_round_key(block, round_key);
+
+        if round < rounds {
+            inv_mix_columns(block);
+        }
+
+        inv_shift_rows(block);
+        inv_sub_bytes(block);
+    }
+
+    add_round_key(block, &round_keys[0]);
+}
+
+// returns the round keys - one for the initial AddRoundKey and one for each round
+pub fn expand_key(key: &[u8]) -> Result<Vec<State>, AesError> {
+    if ![16, 24, 32].contains(&key.len()) {
+        return Err(AesError::InvalidKeySize { size: key.len() });
+    }
+
+    let key_words = key.len() / 4;
+    let rounds = key_words + 6;
+    let mut words = key.chunks_exact(4)
+        .map(|word| [word[0], word[1], word[2], word[3]])
+        .collect::<Vec<[u8; 4]>>();
+
+    for i in key_words..4 * (rounds + 1) {
+        let mut word = words[i - 1];
+
+        if i % key_words == 0 {
+            word.rotate_left(1);
+            word = word.map(|byte| SBOX[byte as usize]);
+            word[0] ^= RCON[i / key_words - 1];
+        } else if key_words > 6 && i % key_words == 4 {
+            word = word.map(|byte| SBOX[byte as usize]);
+        }
+
+        for (byte, previous_byte) in word.iter_mut().zip(words[i - key_words]) {
+            *byte ^= previous_byte;
+        }
+
+        words.push(word);
+    }
+
+    let round_keys = words.chunks_exact(4)
+        .map(|round_words| {
+            let mut round_key = [0u8; BLOCK_SIZE];
+
+            for (i, word) in round_words.iter().enumerate() {
+                round_key[4 * i..4 * i + 4].copy_from_slice(word);
+            }
+
+            round_key
+        })
+        .collect();
+
+    Ok(round_keys)
+}
+
+// multiplication by x in GF(2^8) modulo x^8 + x^4 + x^3 + x + 1
+fn xtime(byte: u8) -> u8 {
+    if byte & 0x80 != 0 {
+        (byte << 1) ^ 0x1b
+    } else {
+        byte << 1
+    }
+}
+
+fn gf_mul(mut x: u8, mut y: u8) -> u8 {
+    let mut product = 0;
+
+    while y != 0 {
+        if y & 1 != 0 {
+            product ^= x;
+        }
+
+        x = xtime(x);
+        y >>= 1;
+    }
+
+    product
+}
+
+// the state is stored column by column - byte (row, column) is at index row + 4*column
+pub fn sub_bytes(state: &mut State) {
+    for byte in state.iter_mut() {
+        *byte = SBOX[*byte as usize];
+    }
+}
+
+pub fn inv_sub_bytes(state: &mut State) {
+    for byte in state.iter_mut() {
+        *byte = INV_SBOX[*byte as usize];
+    }
+}
+
+// row r is rotated left by r positions
+pub fn shift_rows(state: &mut State) {
+    let original = *state;
+
+    for row in 1..4 {
+        for column in 0..4 {
+            state[row + 4 * column] = original[row + 4 * ((column + row) % 4)];
+        }
+    }
+}
+
+pub fn inv_shift_rows(state: &mut State) {
+    let original = *state;
+
+    for row in 1..4 {
+        for column in 0..4 {
+            state[row + 4 * ((column + row) % 4)] = original[row + 4 * column];
+        }
+    }
+}
+
+fn mix_column(column: &mut [u8], coefficients: [u8; 4]) {
+    let original = [column[0], column[1], column[2], column[3]];
+
+    for (row, byte) in column.iter_mut().enumerate() {
+        *byte = (0..4)
+            .map(|i| gf_mul(coefficients[(4 + i - row) % 4], original[i]))
+            .fold(0, |acc, x| acc ^ x);
+    }
+}
+
+pub fn mix_columns(state: &mut State) {
+    for column in state.chunks_exact_mut(4) {
+        mix_column(column, [0x02, 0x03, 0x01, 0x01]);
+    }
+}
+
+pub fn inv_mix_columns(state: &mut State) {
+    for column in state.chunks_exact_mut(4) {
+        mix_column(column, [0x0e, 0x0b, 0x0d, 0x09]);
+    }
+}
+
+pub fn add_round_key(state: &mut State, round_key: &State) {
+    for (byte, key_byte) in state.iter_mut().zip(round_key) {
+        *byte ^= key_byte;
+    }
+}
+
+#[cfg(test)]
+mod test_block_cipher {
+    use crate::util::string_to_hex;
+
+    use super::*;
+
+    fn to_state(hex: &str) -> State {
+        string_to_hex(hex).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_expand_key_128() {
+        let key = string_to_hex("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+
+        let round_keys = expand_key(&key).unwrap();
+
+        assert_eq!(11, round_keys.len());
+        assert_eq!(to_state("a0fafe1788542cb123a339392a6c7605"), round_keys[1]);
+        assert_eq!(to_state("d014f9a8c9ee2589e13f0cc8b6630ca6"), round_keys[10]);
+    }
+
+    #[test]
+    fn test_expand_key_192() {
+        let key = string_to_hex("8e73b0f7da0e6452c810f32b809079e562f8ead2522c6b7b").unwrap();
+
+        let round_keys = expand_key(&key).unwrap();
+
+        assert_eq!(13, round_keys.len());
+        assert_eq!(to_state("e98ba06f448c773c8ecc720401002202"), round_keys[12]);
+    }
+
+    #[test]
+    fn test_expand_key_256() {
+        let key = string_to_hex("603deb1015ca71be2b73aef0857d77811f352c073b6108d72d9810a30914dff4").unwrap();
+
+        let round_keys = expand_key(&key).unwrap();
+
+        assert_eq!(15, round_keys.len());
+        assert_eq!(to_state("fe4890d1e6188d0b046df344706c631e"), round_keys[14]);
+    }
+
+    #[test]
+    fn test_expand_key_error_for_invalid_key_size() {
+        let result = expand_key(&[0u8; 20]);
+
+        assert_eq!(AesError::InvalidKeySize { size: 20 }, result.err().unwrap());
+    }
+
+    #[test]
+    fn test_mix_columns() {
+        let mut state = to_state("d4bf5d30e0b452aeb84111f11e2798e5");
+
+        mix_columns(&mut state);
+
+        assert_eq!(to_state("046681e5e0cb199a48f8d37a2806264c"), state);
+
+        inv_mix_columns(&mut state);
+
+        assert_eq!(to_state("d4bf5d30e0b452aeb84111f11e2798e5"), state);
+    }
+
+    #[test]
+    fn test_shift_rows() {
+        let mut state = to_state("d42711aee0bf98f1b8b45de51e415230");
+
+        shift_rows(&mut state);
+
+        assert_eq!(to_state("d4bf5d30e0b452aeb84111f11e2798e5"), state);
+
+        inv_shift_rows(&mut state);
+
+        assert_eq!(to_state("d42711aee0bf98f1b8b45de51e415230"), state);
+    }
+
+    #[test]
+    fn test_sub_bytes() {
+        let mut state = to_state("193de3bea0f4e22b9ac68d2ae9f84808");
+
+        sub_bytes(&mut state);
+
+        assert_eq!(to_state("d42711aee0bf98f1b8b45de51e415230"), state);
+
+        inv_sub_bytes(&mut state);
+
+        assert_eq!(to_state("193de3bea0f4e22b9ac68d2ae9f84808"), state);
+    }
+
+    #[test]
+    fn test_cipher_example() {
+        let key = string_to_hex("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+        let mut block = to_state("3243f6a8885a308d313198a2e0370734");
+
+        Aes::new(&key).unwrap().encrypt_block(&mut block);
+
+        assert_eq!(to_state("3925841d02dc09fbdc118597196a0b32"), block);
+    }
+
+    #[test]
+    fn test_example_vectors() {
+        let plaintext = to_state("00112233445566778899aabbccddeeff");
+        let vectors = [
+            ("000102030405060708090a0b0c0d0e0f", "69c4e0d86a7b0430d8cdb78070b4c55a"),
+            ("000102030405060708090a0b0c0d0e0f1011121314151617", "dda97ca4864cdfe06eaf70a0ec0d7191"),
+            ("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f", "8ea2b7ca516745bfeafc49904b496089"),
+        ];
+
+        for (key, ciphertext) in vectors {
+            let aes = Aes::new(&string_to_hex(key).unwrap()).unwrap();
+            let mut block = plaintext;
+
+            aes.encrypt_block(&mut block);
+
+            assert_eq!(to_state(ciphertext), block);
+
+            aes.decrypt_block(&mut block);
+
+            assert_eq!(plaintext, block);
+        }
+    }
+}
@@ -1,21 +1,205 @@
-use ecb::cipher::block_padding::Pkcs7;
-use ecb::cipher::{BlockDecryptMut, KeyInit};
+use thiserror::Error;
+
+use crate::xor::xor;
+
+pub mod block_cipher;
+pub mod rounded;
+#[cfg(feature = "rustcrypto")]
+mod rustcrypto;
+
+#[cfg(not(feature = "rustcrypto"))]
+use block_cipher::Aes as Cipher;
+#[cfg(feature = "rustcrypto")]
+use rustcrypto::Aes as Cipher;
+
+pub const BLOCK_SIZE: usize = 16;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AesError {
+    #[error("invalid key size {size}")]
+    InvalidKeySize {
+        size: usize,
+    },
+
+    #[error("ciphertext length has to be a non-zero multiple of the block size")]
+    InvalidLength,
+
+    #[error("invalid padding")]
+    InvalidPadding,
+
+    #[error("invalid number of rounds {rounds}")]
+    InvalidRounds {
+        rounds: usize,
+    },
+}
 
 pub enum Mode {
     ECB,
+    CBC { iv: [u8; BLOCK_SIZE] },
+    CTR { nonce: CtrNonce },
+}
+
+// layout of the nonce and the block counter within the CTR counter block
+#[derive(Clone, Copy)]
+pub enum CtrNonce {
+    // 64-bit little-endian nonce followed by 64-bit little-endian block counter (cryptopals)
+    LittleEndian64 { nonce: u64 },
+
+    // 96-bit nonce followed by 32-bit big-endian block counter (GCM)
+    BigEndian96 { nonce: [u8; 12] },
+}
+
+fn pad(input: &[u8]) -> Vec<u8> {
+    let pad_len = BLOCK_SIZE - (input.len() % BLOCK_SIZE);
+    let mut padded_input = input.to_vec();
+
+    padded_input.extend(vec![pad_len as u8; pad_len]);
+
+    padded_input
+}
+
+fn unpad(input: &[u8]) -> Result<&[u8], AesError> {
+    let pad_len = input[input.len() - 1] as usize;
+
+    if pad_len == 0 || pad_len > BLOCK_SIZE {
+        return Err(AesError::InvalidPadding);
+    }
+
+    let (plaintext, padding) = input.split_at(input.len() - pad_len);
+
+    if padding.iter().any(|byte| *byte as usize != pad_len) {
+        return Err(AesError::InvalidPadding);
+    }
+
+    Ok(plaintext)
+}
+
+fn check_ciphertext(input: &[u8]) -> Result<(), AesError> {
+    if input.is_empty() || !input.len().is_multiple_of(BLOCK_SIZE) {
+        return Err(AesError::InvalidLength);
+    }
+
+    Ok(())
+}
+
+fn encrypt_ecb(input: &[u8], key: &[u8]) -> Result<Vec<u8>, AesError> {
+    let cipher = Cipher::new(key)?;
+    let mut ciphertext = pad(input);
+
+    for block in ciphertext.chunks_exact_mut(BLOCK_SIZE) {
+        cipher.encrypt_block(block.try_into().unwrap());
+    }
+
+    Ok(ciphertext)
 }
 
-fn decrypt_ecb(input: &mut [u8], key: &[u8]) -> Vec<u8> {
-    let plaintext = ecb::Decryptor::<aes::Aes128>::new(key.into())
-        .decrypt_padded_mut::<Pkcs7>(input)
-        .unwrap();
+fn decrypt_ecb(input: &[u8], key: &[u8]) -> Result<Vec<u8>, AesError> {
+    let cipher = Cipher::new(key)?;
+    check_ciphertext(input)?;
+
+    let mut plaintext = input.to_vec();
+
+    for block in plaintext.chunks_exact_mut(BLOCK_SIZE) {
+        cipher.decrypt_block(block.try_into().unwrap());
+    }
 
-    plaintext.to_vec()
+    Ok(unpad(&plaintext)?.to_vec())
 }
 
-pub fn decrypt(input: &mut [u8], key: &[u8], mode: Mode) -> Vec<u8> {
+// each plaintext block is xored with the previous ciphertext block (or the iv) before encryption
+fn encrypt_cbc(input: &[u8], key: &[u8], iv: &[u8; BLOCK_SIZE]) -> Result<Vec<u8>, AesError> {
+    let cipher = Cipher::new(key)?;
+    let mut ciphertext = Vec::with_capacity(input.len() + BLOCK_SIZE);
+    let mut previous_block = *iv;
+
+    for block in pad(input).chunks_exact(BLOCK_SIZE) {
+        let mut block: [u8; BLOCK_SIZE] = xor(block, &previous_block).try_into().unwrap();
+        cipher.encrypt_block(&mut block);
+
+        ciphertext.extend_from_slice(&block);
+        previous_block = block;
+    }
+
+    Ok(ciphertext)
+}
+
+fn decrypt_cbc(input: &[u8], key: &[u8], iv: &[u8; BLOCK_SIZE]) -> Result<Vec<u8>, AesError> {
+    let cipher = Cipher::new(key)?;
+    check_ciphertext(input)?;
+
+    let mut plaintext = Vec::with_capacity(input.len());
+    let mut previous_block: &[u8] = iv;
+
+    for block in input.chunks_exact(BLOCK_SIZE) {
+        let mut decrypted_block: [u8; BLOCK_SIZE] = block.try_into().unwrap();
+        cipher.decrypt_block(&mut decrypted_block);
+
+        plaintext.extend(xor(&decrypted_block, previous_block));
+        previous_block = block;
+    }
+
+    Ok(unpad(&plaintext)?.to_vec())
+}
+
+fn counter_block(nonce: &CtrNonce, counter: u64) -> [u8; BLOCK_SIZE] {
+    let mut block = [0u8; BLOCK_SIZE];
+
+    match nonce {
+        CtrNonce::LittleEndian64 { nonce } => {
+            block[..8].copy_from_slice(&nonce.to_le_bytes());
+            block[8..].copy_from_slice(&counter.to_le_bytes());
+        },
+        CtrNonce::BigEndian96 { nonce } => {
+            block[..12].copy_from_slice(nonce);
+            block[12..].copy_from_slice(&(counter as u32).to_be_bytes());
+        },
+    }
+
+    block
+}
+
+// encryption and decryption are the same operation - the input is xored with the encrypted counter blocks
+fn apply_ctr(input: &[u8], key: &[u8], nonce: &CtrNonce) -> Result<Vec<u8>, AesError> {
+    apply_ctr_at(input, key, nonce, 0)
+}
+
+// applies the keystream starting at the given byte offset of the stream - the counter seeks straight
+// to the block containing the offset, so nothing before it gets generated
+pub fn apply_ctr_at(input: &[u8], key: &[u8], nonce: &CtrNonce, offset: usize) -> Result<Vec<u8>, AesError> {
+    let cipher = Cipher::new(key)?;
+    let mut output = Vec::with_capacity(input.len());
+    let mut counter = (offset / BLOCK_SIZE) as u64;
+    let mut block_offset = offset % BLOCK_SIZE;
+    let mut remaining_input = input;
+
+    while !remaining_input.is_empty() {
+        let mut keystream = counter_block(nonce, counter);
+        cipher.encrypt_block(&mut keystream);
+
+        let (block, rest) = remaining_input.split_at(remaining_input.len().min(BLOCK_SIZE - block_offset));
+        output.extend(xor(block, &keystream[block_offset..]));
+
+        remaining_input = rest;
+        block_offset = 0;
+        counter += 1;
+    }
+
+    Ok(output)
+}
+
+pub fn encrypt(input: &[u8], key: &[u8], mode: Mode) -> Result<Vec<u8>, AesError> {
+    match mode {
+        Mode::ECB => encrypt_ecb(input, key),
+        Mode::CBC { iv } => encrypt_cbc(input, key, &iv),
+        Mode::CTR { nonce } => apply_ctr(input, key, &nonce),
+    }
+}
+
+pub fn decrypt(input: &[u8], key: &[u8], mode: Mode) -> Result<Vec<u8>, AesError> {
     match mode {
         Mode::ECB => decrypt_ecb(input, key),
+        Mode::CBC { iv } => decrypt_cbc(input, key, &iv),
+        Mode::CTR { nonce } => apply_ctr(input, key, &nonce),
     }
 }
 
@@ -35,24 +219,212 @@ pub fn is_ecb_encrypted(input: &[u8]) -> bool {
 
 #[cfg(test)]
 mod test_aes {
+    use rand::{Rng, rngs::ThreadRng};
+
     use super::*;
 
     #[test]
     fn test_decrypt_ecb() {
         let base64_input = std::fs::read_to_string("input/7.txt").unwrap().replace("\n", "");
-        let mut input = crate::base64::decode(base64_input.as_bytes()).unwrap();
+        let input = crate::base64::decode(base64_input.as_bytes()).unwrap();
         let key = "YELLOW SUBMARINE";
 
-        let plaintext = decrypt(&mut input, key.as_bytes(), Mode::ECB);
+        let plaintext = decrypt(&input, key.as_bytes(), Mode::ECB).unwrap();
         let plaintext = String::from_utf8_lossy(&plaintext);
 
         assert!(plaintext.is_ascii());
     }
 
+    #[test]
+    fn test_encrypt_cbc_first_block_is_ecb_of_plaintext_xor_iv() {
+        let key = "YELLOW SUBMARINE".as_bytes();
+        let iv = [0x42u8; BLOCK_SIZE];
+        let plaintext = "sixteen byte msg".as_bytes();
+
+        let cbc_ciphertext = encrypt(plaintext, key, Mode::CBC { iv }).unwrap();
+        let ecb_ciphertext = encrypt(&xor(plaintext, &iv), key, Mode::ECB).unwrap();
+
+        assert_eq!(2 * BLOCK_SIZE, cbc_ciphertext.len());
+        assert_eq!(ecb_ciphertext[..BLOCK_SIZE], cbc_ciphertext[..BLOCK_SIZE]);
+    }
+
+    #[test]
+    fn test_decrypt_cbc() {
+        let key = "YELLOW SUBMARINE".as_bytes();
+        let iv = [0u8; BLOCK_SIZE];
+        let plaintext = "I'm back and I'm ringin' the bell, a rockin' on the mike while the fly girls yell".as_bytes();
+
+        let ciphertext = encrypt(plaintext, key, Mode::CBC { iv }).unwrap();
+        let decrypted = decrypt(&ciphertext, key, Mode::CBC { iv }).unwrap();
+
+        assert_eq!(plaintext, decrypted);
+    }
+
+    #[test]
+    fn test_encrypt_ecb_pads_full_block_when_aligned() {
+        let key = "YELLOW SUBMARINE".as_bytes();
+        let plaintext = [0u8; 2 * BLOCK_SIZE];
+
+        let ciphertext = encrypt(&plaintext, key, Mode::ECB).unwrap();
+
+        assert_eq!(3 * BLOCK_SIZE, ciphertext.len());
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_ecb_fuzz() {
+        let mut rng = rand::rng();
+
+        for _ in 0..1000 {
+            let key: [u8; BLOCK_SIZE] = rng.random();
+            let input = generate_random_input(&mut rng);
+            let ciphertext = encrypt(&input, &key, Mode::ECB).unwrap();
+
+            assert_eq!(input, decrypt(&ciphertext, &key, Mode::ECB).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_cbc_fuzz() {
+        let mut rng = rand::rng();
+
+        for _ in 0..1000 {
+            let key: [u8; BLOCK_SIZE] = rng.random();
+            let iv: [u8; BLOCK_SIZE] = rng.random();
+            let input = generate_random_input(&mut rng);
+            let ciphertext = encrypt(&input, &key, Mode::CBC { iv }).unwrap();
+
+            assert_eq!(input, decrypt(&ciphertext, &key, Mode::CBC { iv }).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_decrypt_ctr() {
+        let base64_input = "L77na/nrFsKvynd6HzOoG7GHTLXsTVu9qvY/2syLXzhPweyyMTJULu/6/kXX0KSvoOLSFQ==";
+        let input = crate::base64::decode(base64_input.as_bytes()).unwrap();
+        let key = "YELLOW SUBMARINE".as_bytes();
+        let nonce = CtrNonce::LittleEndian64 { nonce: 0 };
+
+        let plaintext = decrypt(&input, key, Mode::CTR { nonce }).unwrap();
+
+        assert_eq!("Yo, VIP Let's kick it Ice, Ice, baby Ice, Ice, baby ", String::from_utf8_lossy(&plaintext));
+    }
+
+    #[test]
+    fn test_ctr_counter_block_little_endian_64() {
+        let nonce = CtrNonce::LittleEndian64 { nonce: 0x0102 };
+
+        let block = counter_block(&nonce, 0x0304);
+
+        assert_eq!([2, 1, 0, 0, 0, 0, 0, 0, 4, 3, 0, 0, 0, 0, 0, 0], block);
+    }
+
+    #[test]
+    fn test_ctr_counter_block_big_endian_96() {
+        let nonce = CtrNonce::BigEndian96 { nonce: [0xca; 12] };
+
+        let block = counter_block(&nonce, 0x0304);
+
+        assert_eq!([0xca, 0xca, 0xca, 0xca, 0xca, 0xca, 0xca, 0xca, 0xca, 0xca, 0xca, 0xca, 0, 0, 3, 4], block);
+    }
+
+    #[test]
+    fn test_apply_ctr_at_matches_keystream_slice() {
+        let key = "YELLOW SUBMARINE".as_bytes();
+        let nonce = CtrNonce::LittleEndian64 { nonce: 7 };
+        let keystream = encrypt(&[0u8; 6 * BLOCK_SIZE], key, Mode::CTR { nonce }).unwrap();
+
+        for offset in 0..3 * BLOCK_SIZE {
+            for len in [0, 1, BLOCK_SIZE - 1, BLOCK_SIZE, 2 * BLOCK_SIZE + 3] {
+                let partial_keystream = apply_ctr_at(&vec![0u8; len], key, &nonce, offset).unwrap();
+
+                assert_eq!(keystream[offset..offset + len], partial_keystream);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ctr_keeps_input_length() {
+        let key = "YELLOW SUBMARINE".as_bytes();
+        let nonce = CtrNonce::BigEndian96 { nonce: [0u8; 12] };
+
+        for len in 0..=2 * BLOCK_SIZE {
+            let ciphertext = encrypt(&vec![0u8; len], key, Mode::CTR { nonce }).unwrap();
+
+            assert_eq!(len, ciphertext.len());
+        }
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_192_and_256_bit_keys() {
+        let plaintext = "YELLOW SUBMARINE".as_bytes();
+
+        for key in ["YELLOW SUBMARINE YELLOW ", "YELLOW SUBMARINE YELLOW SUBMARIN"] {
+            let ciphertext = encrypt(plaintext, key.as_bytes(), Mode::ECB).unwrap();
+
+            assert_eq!(plaintext, decrypt(&ciphertext, key.as_bytes(), Mode::ECB).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_error_for_invalid_key_size() {
+        let key = "YELLOW SUB".as_bytes();
+
+        let encrypt_result = encrypt("plaintext".as_bytes(), key, Mode::ECB);
+        let decrypt_result = decrypt(&[0u8; BLOCK_SIZE], key, Mode::CBC { iv: [0u8; BLOCK_SIZE] });
+
+        assert_eq!(AesError::InvalidKeySize { size: 10 }, encrypt_result.err().unwrap());
+        assert_eq!(AesError::InvalidKeySize { size: 10 }, decrypt_result.err().unwrap());
+    }
+
+    #[test]
+    fn test_decrypt_error_for_unaligned_ciphertext() {
+        let key = "YELLOW SUBMARINE".as_bytes();
+
+        let ecb_result = decrypt(&[0u8; BLOCK_SIZE + 1], key, Mode::ECB);
+        let cbc_result = decrypt(&[], key, Mode::CBC { iv: [0u8; BLOCK_SIZE] });
+
+        assert_eq!(AesError::InvalidLength, ecb_result.err().unwrap());
+        assert_eq!(AesError::InvalidLength, cbc_result.err().unwrap());
+    }
+
+    #[test]
+    fn test_decrypt_error_for_invalid_padding() {
+        let key = "YELLOW SUBMARINE".as_bytes();
+        let iv = [0u8; BLOCK_SIZE];
+
+        // the first block of an all-zero plaintext decrypts to a last byte of 0x00
+        let ecb_ciphertext = encrypt(&[0u8; BLOCK_SIZE], key, Mode::ECB).unwrap();
+        let cbc_ciphertext = encrypt(&[0u8; BLOCK_SIZE], key, Mode::CBC { iv }).unwrap();
+
+        let ecb_result = decrypt(&ecb_ciphertext[..BLOCK_SIZE], key, Mode::ECB);
+        let cbc_result = decrypt(&cbc_ciphertext[..BLOCK_SIZE], key, Mode::CBC { iv });
+
+        assert_eq!(AesError::InvalidPadding, ecb_result.err().unwrap());
+        assert_eq!(AesError::InvalidPadding, cbc_result.err().unwrap());
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_ctr_fuzz() {
+        let mut rng = rand::rng();
+
+        for _ in 0..1000 {
+            let key: [u8; BLOCK_SIZE] = rng.random();
+            let nonce = if rng.random() {
+                CtrNonce::LittleEndian64 { nonce: rng.random() }
+            } else {
+                CtrNonce::BigEndian96 { nonce: rng.random() }
+            };
+            let input = generate_random_input(&mut rng);
+            let ciphertext = encrypt(&input, &key, Mode::CTR { nonce }).unwrap();
+
+            assert_eq!(input, decrypt(&ciphertext, &key, Mode::CTR { nonce }).unwrap());
+        }
+    }
+
     #[test]
     fn test_ecb_encrypted_true_for_ecb() {
         let input = "d880619740a8a19b7840a8a31c810a3d08649af70dc06f4fd5d2d69c744cd283e2dd052f6b641dbf9d11b0348542bb5708649af70dc06f4fd5d2d69c744cd2839475c9dfdbc1d46597949d9c7e82bf5a08649af70dc06f4fd5d2d69c744cd28397a93eab8d6aecd566489154789a6b0308649af70dc06f4fd5d2d69c744cd283d403180c98c8f6db1f2a3f9c4040deb0ab51b29933f2c123c58386b06fba186a";
-        let hex_input = crate::util::string_to_hex(&input).unwrap();
+        let hex_input = crate::util::string_to_hex(input).unwrap();
 
         assert!(is_ecb_encrypted(&hex_input));
     }
@@ -60,8 +432,19 @@ mod test_aes {
     #[test]
     fn test_ecb_encrypted_false_for_not_ecb() {
         let input = "9eed35024a40add6409a9690e570ef357dfc0b38491706783dbb6043bd4fcdaf01986fcccbf89f15bc53fe4aff70821b309aa5cec59ef3c588c1042593f9994644bca862152a20bf94dc0d288176eb9f49b7f814bf35050e83b139d2dbd5f08d3cef35e271ccc6d8074fc5fe1570886a0746ce19be8cea27c4382bd04d8d45c7b7fd9e3e89ad38eb37656577395fa0062e5f8e15be2c9a4833bb1f2fce90bb86";
-        let hex_input = crate::util::string_to_hex(&input).unwrap();
+        let hex_input = crate::util::string_to_hex(input).unwrap();
 
         assert!(!is_ecb_encrypted(&hex_input));
     }
-}
\ No newline at end of file
+
+    fn generate_random_input(rng: &mut ThreadRng) -> Vec<u8> {
+        let len = rng.random_range(0..=100);
+        let mut input = Vec::new();
+
+        for _ in 0..len {
+            input.push(rng.random());
+        }
+
+        input
+    }
+}
@@ -0,0 +1,131 @@
+use super::block_cipher::{decrypt_rounds, encrypt_rounds, expand_key, State};
+use super::AesError;
+
+// AES reduced to the first N rounds of the key's full round count, for square and differential experiments
+// like the full cipher, the last round skips MixColumns
+pub struct RoundedAes {
+    round_keys: Vec<State>,
+}
+
+impl RoundedAes {
+    pub fn new(key: &[u8], rounds: usize) -> Result<Self, AesError> {
+        let mut round_keys = expand_key(key)?;
+
+        if rounds == 0 || rounds >= round_keys.len() {
+            return Err(AesError::InvalidRounds { rounds });
+        }
+
+        round_keys.truncate(rounds + 1);
+
+        Ok(RoundedAes { round_keys })
+    }
+
+    pub fn rounds(&self) -> usize {
+        self.round_keys.len() - 1
+    }
+
+    pub fn round_key(&self, round: usize) -> &State {
+        &self.round_keys[round]
+    }
+
+    pub fn encrypt_block(&self, block: &mut State) {
+        encrypt_rounds(block, &self.round_keys, |_, _| {});
+    }
+
+    // the observer gets the round number and the state after that round, round 0 being the initial AddRoundKey
+    pub fn encrypt_block_observed(&self, block: &mut State, observer: impl FnMut(usize, &State)) {
+        encrypt_rounds(block, &self.round_keys, observer);
+    }
+
+    // returns the states after the initial AddRoundKey and after every round
+    pub fn round_states(&self, block: &State) -> Vec<State> {
+        let mut block = *block;
+        let mut states = Vec::with_capacity(self.round_keys.len());
+
+        self.encrypt_block_observed(&mut block, |_, state| states.push(*state));
+
+        states
+    }
+
+    pub fn decrypt_block(&self, block: &mut State) {
+        decrypt_rounds(block, &self.round_keys);
+    }
+}
+
+#[cfg(test)]
+mod test_rounded {
+    use crate::aes::block_cipher::{add_round_key, inv_shift_rows, inv_sub_bytes, Aes};
+    use crate::util::string_to_hex;
+
+    use super::*;
+
+    fn to_state(hex: &str) -> State {
+        string_to_hex(hex).unwrap().try_into().unwrap()
+    }
+
+    #[test]
+    fn test_error_for_invalid_rounds() {
+        let key = [0u8; 16];
+
+        assert_eq!(AesError::InvalidRounds { rounds: 0 }, RoundedAes::new(&key, 0).err().unwrap());
+        assert_eq!(AesError::InvalidRounds { rounds: 11 }, RoundedAes::new(&key, 11).err().unwrap());
+    }
+
+    #[test]
+    fn test_full_rounds_matches_aes() {
+        let key = string_to_hex("000102030405060708090a0b0c0d0e0f").unwrap();
+        let mut rounded_block = to_state("00112233445566778899aabbccddeeff");
+        let mut block = rounded_block;
+
+        RoundedAes::new(&key, 10).unwrap().encrypt_block(&mut rounded_block);
+        Aes::new(&key).unwrap().encrypt_block(&mut block);
+
+        assert_eq!(to_state("69c4e0d86a7b0430d8cdb78070b4c55a"), rounded_block);
+        assert_eq!(block, rounded_block);
+    }
+
+    #[test]
+    fn test_round_states() {
+        // FIPS-197 appendix B - start of round states
+        let key = string_to_hex("2b7e151628aed2a6abf7158809cf4f3c").unwrap();
+        let aes = RoundedAes::new(&key, 3).unwrap();
+
+        let states = aes.round_states(&to_state("3243f6a8885a308d313198a2e0370734"));
+
+        assert_eq!(4, states.len());
+        assert_eq!(to_state("193de3bea0f4e22b9ac68d2ae9f84808"), states[0]);
+        assert_eq!(to_state("a49c7ff2689f352b6b5bea43026a5049"), states[1]);
+        assert_eq!(to_state("aa8f5f0361dde3ef82d24ad26832469a"), states[2]);
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_reduced_rounds() {
+        let key = string_to_hex("000102030405060708090a0b0c0d0e0f1011121314151617").unwrap();
+        let plaintext = to_state("00112233445566778899aabbccddeeff");
+
+        for rounds in 1..=12 {
+            let aes = RoundedAes::new(&key, rounds).unwrap();
+            let mut block = plaintext;
+
+            aes.encrypt_block(&mut block);
+            aes.decrypt_block(&mut block);
+
+            assert_eq!(plaintext, block);
+        }
+    }
+
+    #[test]
+    fn test_peeling_last_round_reveals_previous_state() {
+        let key = [7u8; 16];
+        let aes = RoundedAes::new(&key, 4).unwrap();
+        let plaintext = [1u8; 16];
+        let states = aes.round_states(&plaintext);
+        let mut block = states[4];
+
+        add_round_key(&mut block, aes.round_key(4));
+        inv_shift_rows(&mut block);
+        inv_sub_bytes(&mut block);
+
+        assert_eq!(states[3], block);
+    }
+}
@@ -0,0 +1,37 @@
+use aes::cipher::{BlockDecrypt, BlockEncrypt, KeyInit};
+
+use super::{AesError, BLOCK_SIZE};
+
+// block cipher backed by the aes crate, kept for comparison with our own implementation
+pub enum Aes {
+    Aes128(aes::Aes128),
+    Aes192(aes::Aes192),
+    Aes256(aes::Aes256),
+}
+
+impl Aes {
+    pub fn new(key: &[u8]) -> Result<Self, AesError> {
+        match key.len() {
+            16 => Ok(Aes::Aes128(aes::Aes128::new(key.into()))),
+            24 => Ok(Aes::Aes192(aes::Aes192::new(key.into()))),
+            32 => Ok(Aes::Aes256(aes::Aes256::new(key.into()))),
+            size => Err(AesError::InvalidKeySize { size }),
+        }
+    }
+
+    pub fn encrypt_block(&self, block: &mut [u8; BLOCK_SIZE]) {
+        match self {
+            Aes::Aes128(cipher) => cipher.encrypt_block(block.into()),
+            Aes::Aes192(cipher) => cipher.encrypt_block(block.into()),
+            Aes::Aes256(cipher) => cipher.encrypt_block(block.into()),
+        }
+    }
+
+    pub fn decrypt_block(&self, block: &mut [u8; BLOCK_SIZE]) {
+        match self {
+            Aes::Aes128(cipher) => cipher.decrypt_block(block.into()),
+            Aes::Aes192(cipher) => cipher.decrypt_block(block.into()),
+            Aes::Aes256(cipher) => cipher.decrypt_block(block.into()),
+        }
+    }
+}
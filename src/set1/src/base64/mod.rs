@@ -78,7 +78,7 @@ pub fn encode(input: &[u8]) -> String {
 }
 
 pub fn decode(base64_input: &[u8]) -> Result<Vec<u8>, Base64Error> {
-    if base64_input.len() * 6 % 8 != 0 {
+    if !(base64_input.len() * 6).is_multiple_of(8) {
         return Err(Base64Error::InvalidLength);
     }
 
@@ -106,7 +106,7 @@ pub fn decode(base64_input: &[u8]) -> Result<Vec<u8>, Base64Error> {
 
     // remove the trailing 0 bits added when base64 padded to the byte boundary
     if contains_padding {
-        while decoded_bits.len() % 8 != 0 {
+        while !decoded_bits.len().is_multiple_of(8) {
             decoded_bits.pop();
         }
     }
@@ -6,7 +6,7 @@ use crate::xor::xor;
 
 static LETTER_FREQUENCIES: Lazy<HashMap<u8, f64>> = Lazy::new(|| {
     let mut frequency_map = HashMap::new();
-    let input = std::fs::read_to_string("src/crack_xor/sample_english_input.txt").unwrap();
+    let input = include_str!("sample_english_input.txt");
 
     for b in input.bytes() {
         frequency_map.entry(b).and_modify(|count| { *count += 1.0 }).or_insert(1.0);
@@ -45,11 +45,11 @@ fn input_error(input: &[u8]) -> f64 {
     error 
 }
 
-fn guess_key(input: &[u8]) -> u8 {
+pub fn guess_key(input: &[u8]) -> u8 {
     let mut key = b'a';
     let mut min_error= 999999999999.9;
 
-    for candidate_key in 0u8..255 {
+    for candidate_key in 0u8..=255 {
         let xor_input = xor(input, &[candidate_key]);
         let error = input_error(&xor_input);
 
@@ -144,6 +144,22 @@ pub fn crack_single_byte_xors(inputs: &Vec<Vec<u8>>) -> Vec<u8> {
     result 
 }
 
+// guesses every key byte on its own from the input bytes xored with it
+pub fn guess_repeating_key(input: &[u8], key_size: usize) -> Vec<u8> {
+    let mut key = Vec::with_capacity(key_size);
+
+    for i in 0..key_size {
+        let block_input = input[i..].iter()
+            .step_by(key_size)
+            .copied()
+            .collect::<Vec<u8>>();
+
+        key.push(guess_key(&block_input));
+    }
+
+    key
+}
+
 pub fn crack_multi_byte_xor(input: &[u8]) -> Vec<u8> {
     let mut guessed_key = vec![0u8];
     let mut min_error = f64::MAX;
@@ -151,18 +167,7 @@ pub fn crack_multi_byte_xor(input: &[u8]) -> Vec<u8> {
     let key_sizes = guess_key_size(input)[..5].to_vec();
 
     for key_size in key_sizes {
-        let mut key = Vec::new();
-
-        for i in 0..key_size {
-            let block_input = input[i..].iter()
-                .step_by(key_size)
-                .copied()
-                .collect::<Vec<u8>>();
-
-            let key_byte = guess_key(&block_input);
-            key.push(key_byte);
-        }
-
+        let key = guess_repeating_key(input, key_size);
         let deciphered_input = xor(input, &key);
         let error = input_error(&deciphered_input);
 
@@ -209,6 +214,15 @@ mod crack_test {
         Ok(())
     }
 
+    #[test]
+    fn test_guess_repeating_key() {
+        let input = std::fs::read_to_string("src/crack_xor/sample_english_input.txt").unwrap();
+        let key = [0x00, 0x7f, 0xff];
+        let enciphred_input = crate::xor::xor(input.as_bytes(), &key);
+
+        assert_eq!(key.to_vec(), guess_repeating_key(&enciphred_input, key.len()));
+    }
+
     #[test]
     fn test_guess_key_size() {
         let input = std::fs::read_to_string("src/crack_xor/sample_english_input.txt").unwrap();
@@ -1,3 +1,5 @@
+pub mod aes;
 pub mod base64;
+pub mod crack_xor;
 pub mod util;
-pub mod xor;
\ No newline at end of file
+pub mod xor;
@@ -1,10 +1,6 @@
 use anyhow::Result;
 
-mod aes;
-mod base64;
-mod xor;
-mod crack_xor;
-mod util;
+use set1::{aes, base64, crack_xor, util, xor};
 
 fn main() -> Result<()> {
     // challenge 1 - convert hex to base64
@@ -54,8 +50,8 @@ fn main() -> Result<()> {
     // challenge 7 - AES ECB cipher
     let base64_input = std::fs::read_to_string("input/7.txt").unwrap().replace("\n", "");
     let key = "YELLOW SUBMARINE";
-    let mut input = base64::decode(base64_input.as_bytes()).unwrap();
-    let decrypted_input = aes::decrypt(&mut input, key.as_bytes(), aes::Mode::ECB);
+    let input = base64::decode(base64_input.as_bytes()).unwrap();
+    let decrypted_input = aes::decrypt(&input, key.as_bytes(), aes::Mode::ECB)?;
 
     println!("{}", String::from_utf8_lossy(&decrypted_input));
 
@@ -72,7 +72,7 @@ fn from_hex(hex: &u8) -> [u8; 2] {
 pub fn string_to_hex(hex_string: &str) -> Result<Vec<u8>, HexConversionError> {
     let mut hex = Vec::new();
 
-    if hex_string.len() % 2 != 0 {
+    if !hex_string.len().is_multiple_of(2) {
         return Err(HexConversionError::InvalidLength);
     }
 
@@ -0,0 +1,136 @@
+use rand::Rng;
+use set1::aes::{self, AesError, BLOCK_SIZE, Mode};
+use set1::xor::xor;
+
+pub const COMMENT_PREFIX: &str = "comment1=cooking%20MCs;userdata=";
+pub const COMMENT_SUFFIX: &str = ";comment2=%20like%20a%20pound%20of%20bacon";
+
+// quotes out the metacharacters so the user data can't add pairs of its own
+pub fn quote(userdata: &str) -> String {
+    userdata.replace(';', "%3B").replace('=', "%3D")
+}
+
+pub fn comment_for(userdata: &str) -> String {
+    format!("{COMMENT_PREFIX}{}{COMMENT_SUFFIX}", quote(userdata))
+}
+
+// works on raw bytes since a tampered ciphertext decrypts to blocks of garbage
+pub fn is_admin(plaintext: &[u8]) -> bool {
+    plaintext.split(|byte| *byte == b';').any(|pair| pair == b"admin=true")
+}
+
+// encrypts comments under a random key and iv only the service knows
+pub struct CbcCommentService {
+    key: [u8; BLOCK_SIZE],
+    iv: [u8; BLOCK_SIZE],
+}
+
+impl CbcCommentService {
+    pub fn new() -> Self {
+        let mut rng = rand::rng();
+
+        CbcCommentService { key: rng.random(), iv: rng.random() }
+    }
+
+    pub fn encrypt_userdata(&self, userdata: &str) -> Vec<u8> {
+        aes::encrypt(comment_for(userdata).as_bytes(), &self.key, Mode::CBC { iv: self.iv }).unwrap()
+    }
+
+    pub fn is_admin(&self, ciphertext: &[u8]) -> Result<bool, AesError> {
+        let plaintext = aes::decrypt(ciphertext, &self.key, Mode::CBC { iv: self.iv })?;
+
+        Ok(is_admin(&plaintext))
+    }
+}
+
+impl Default for CbcCommentService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// flipping a bit in ciphertext block i flips the same bit in plaintext block i + 1
+// (while scrambling plaintext block i), so xoring block i with known ^ desired turns
+// the known bytes at the start of the next plaintext block into the desired ones
+pub fn cbc_bitflip(ciphertext: &[u8], block_index: usize, known: &[u8], desired: &[u8]) -> Vec<u8> {
+    let start = block_index * BLOCK_SIZE;
+    let delta = xor(known, desired);
+    let mut flipped_ciphertext = ciphertext.to_vec();
+
+    for (byte, delta_byte) in flipped_ciphertext[start..start + delta.len()].iter_mut().zip(delta) {
+        *byte ^= delta_byte;
+    }
+
+    flipped_ciphertext
+}
+
+// the user data fills up the block the prefix ends in, then adds a block to sacrifice and a block
+// with placeholders for the metacharacters that get flipped into ";admin=true;"
+pub fn forge_admin(oracle: impl Fn(&str) -> Vec<u8>) -> Vec<u8> {
+    let known = "XadminXtrueX";
+    let desired = ";admin=true;";
+    let filler_len = (BLOCK_SIZE - COMMENT_PREFIX.len() % BLOCK_SIZE) % BLOCK_SIZE;
+    let sacrificed_block_index = (COMMENT_PREFIX.len() + filler_len) / BLOCK_SIZE;
+
+    let userdata = format!("{}{known}", "A".repeat(filler_len + BLOCK_SIZE));
+    let ciphertext = oracle(&userdata);
+
+    cbc_bitflip(&ciphertext, sacrificed_block_index, known.as_bytes(), desired.as_bytes())
+}
+
+#[cfg(test)]
+mod test_bitflipping {
+    use super::*;
+
+    #[test]
+    fn test_quote() {
+        assert_eq!("%3Badmin%3Dtrue%3B", quote(";admin=true;"));
+    }
+
+    #[test]
+    fn test_comment_for() {
+        let comment = comment_for("foo");
+
+        assert_eq!("comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon", comment);
+    }
+
+    #[test]
+    fn test_is_admin() {
+        assert!(is_admin(b"comment1=x;admin=true;comment2=y"));
+        assert!(is_admin(b"\xff\x00garbage;admin=true"));
+        assert!(!is_admin(b"comment1=x;admin=false;comment2=y"));
+        assert!(!is_admin(comment_for(";admin=true;").as_bytes()));
+    }
+
+    #[test]
+    fn test_service_not_admin_for_injected_userdata() {
+        let service = CbcCommentService::new();
+
+        let ciphertext = service.encrypt_userdata(";admin=true;");
+
+        assert_eq!(Ok(false), service.is_admin(&ciphertext));
+    }
+
+    #[test]
+    fn test_cbc_bitflip() {
+        let key = [0u8; BLOCK_SIZE];
+        let iv = [0u8; BLOCK_SIZE];
+        let plaintext = "0123456789abcdef0123456789abcdef".as_bytes();
+        let ciphertext = aes::encrypt(plaintext, &key, Mode::CBC { iv }).unwrap();
+
+        let flipped_ciphertext = cbc_bitflip(&ciphertext, 0, b"0123", b"wxyz");
+        let flipped_plaintext = aes::decrypt(&flipped_ciphertext, &key, Mode::CBC { iv }).unwrap();
+
+        assert_ne!(plaintext[..BLOCK_SIZE], flipped_plaintext[..BLOCK_SIZE]);
+        assert_eq!("wxyz456789abcdef".as_bytes(), &flipped_plaintext[BLOCK_SIZE..]);
+    }
+
+    #[test]
+    fn test_forge_admin() {
+        let service = CbcCommentService::new();
+
+        let forged_ciphertext = forge_admin(|userdata| service.encrypt_userdata(userdata));
+
+        assert_eq!(Ok(true), service.is_admin(&forged_ciphertext));
+    }
+}
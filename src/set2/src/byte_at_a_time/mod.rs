@@ -0,0 +1,364 @@
+use std::cell::Cell;
+
+use rand::Rng;
+use set1::aes::{self, BLOCK_SIZE, Mode};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AttackError {
+    #[error("could not determine the oracle block size")]
+    UnknownBlockSize,
+
+    #[error("oracle does not encrypt in ECB mode")]
+    NotEcb,
+
+    #[error("could not align the attacker blocks after the prefix")]
+    UnknownPrefix,
+}
+
+const FILLER: u8 = b'A';
+const MARKERS: [u8; 2] = [b'B', b'C'];
+const MAX_ALIGNMENT_ATTEMPTS: usize = 1024;
+
+pub struct RecoveredSuffix {
+    pub plaintext: Vec<u8>,
+    pub block_size: usize,
+    pub oracle_queries: usize,
+}
+
+// returns an oracle encrypting the input followed by the secret suffix under a random key in ECB mode
+pub fn ecb_suffix_oracle(suffix: Vec<u8>) -> impl Fn(&[u8]) -> Vec<u8> {
+    let key: [u8; BLOCK_SIZE] = rand::rng().random();
+
+    move |input: &[u8]| {
+        let mut plaintext = input.to_vec();
+        plaintext.extend_from_slice(&suffix);
+
+        aes::encrypt(&plaintext, &key, Mode::ECB).unwrap()
+    }
+}
+
+// returns an oracle encrypting a random prefix, the input and the secret suffix under a random key in ECB mode
+// the prefix length is either picked once or for every call
+pub fn ecb_prefix_suffix_oracle(suffix: Vec<u8>, max_prefix_len: usize, random_per_call: bool) -> impl Fn(&[u8]) -> Vec<u8> {
+    let mut rng = rand::rng();
+    let key: [u8; BLOCK_SIZE] = rng.random();
+    let fixed_prefix = random_bytes(&mut rng, max_prefix_len);
+
+    move |input: &[u8]| {
+        let mut plaintext = if random_per_call {
+            random_bytes(&mut rand::rng(), max_prefix_len)
+        } else {
+            fixed_prefix.clone()
+        };
+        plaintext.extend_from_slice(input);
+        plaintext.extend_from_slice(&suffix);
+
+        aes::encrypt(&plaintext, &key, Mode::ECB).unwrap()
+    }
+}
+
+fn random_bytes(rng: &mut impl Rng, max_len: usize) -> Vec<u8> {
+    let len = rng.random_range(0..=max_len);
+
+    (0..len).map(|_| rng.random()).collect()
+}
+
+fn gcd(x: usize, y: usize) -> usize {
+    if y == 0 { x } else { gcd(y, x % y) }
+}
+
+// the ciphertext length only ever changes by multiples of the block size, even when a random
+// prefix makes it change between calls
+pub fn find_block_size(oracle: impl Fn(&[u8]) -> Vec<u8>) -> Result<usize, AttackError> {
+    let base_len = oracle(&[]).len();
+    let mut block_size = 0;
+
+    for input_len in 1..=64 {
+        let len = oracle(&vec![FILLER; input_len]).len();
+
+        block_size = gcd(block_size, len.abs_diff(base_len));
+    }
+
+    if block_size == 0 {
+        return Err(AttackError::UnknownBlockSize);
+    }
+
+    Ok(block_size)
+}
+
+pub fn is_ecb_oracle(oracle: impl Fn(&[u8]) -> Vec<u8>, block_size: usize) -> bool {
+    aes::is_ecb_encrypted(&oracle(&vec![b'A'; 3 * block_size]))
+}
+
+// the number of input bytes needed to grow the ciphertext by a block gives away the suffix length
+fn find_suffix_len(oracle: &impl Fn(&[u8]) -> Result<Vec<u8>, AttackError>, block_size: usize) -> Result<usize, AttackError> {
+    let base_len = oracle(&[])?.len();
+
+    for input_len in 1..=block_size {
+        if oracle(&vec![b'A'; input_len])?.len() > base_len {
+            return Ok(base_len - input_len);
+        }
+    }
+
+    Ok(base_len - block_size)
+}
+
+// recovers the suffix appended by an oracle of the form ECB(input || suffix)
+// each unknown byte is pushed to the end of a block and matched against all 256 candidate blocks
+pub fn recover_suffix(oracle: impl Fn(&[u8]) -> Result<Vec<u8>, AttackError>, block_size: usize) -> Result<Vec<u8>, AttackError> {
+    let suffix_len = find_suffix_len(&oracle, block_size)?;
+    let mut recovered = vec![b'A'; block_size - 1];
+
+    for i in 0..suffix_len {
+        let shim = vec![b'A'; block_size - 1 - (i % block_size)];
+        let block_start = (i / block_size) * block_size;
+        let ciphertext = oracle(&shim)?;
+        let target_block = &ciphertext[block_start..block_start + block_size];
+
+        let mut candidate = recovered[recovered.len() - (block_size - 1)..].to_vec();
+        candidate.push(0);
+
+        let mut next_byte = None;
+
+        for byte in 0..=255u8 {
+            candidate[block_size - 1] = byte;
+
+            if oracle(&candidate)?[..block_size] == *target_block {
+                next_byte = Some(byte);
+                break;
+            }
+        }
+
+        match next_byte {
+            Some(byte) => recovered.push(byte),
+            None => break,
+        }
+    }
+
+    Ok(recovered.split_off(block_size - 1))
+}
+
+fn blocks(ciphertext: &[u8], block_size: usize) -> Vec<&[u8]> {
+    ciphertext.chunks_exact(block_size).collect()
+}
+
+// filler bytes followed by two blocks of each marker byte
+fn marker_input(pad_len: usize, block_size: usize) -> Vec<u8> {
+    let mut input = vec![FILLER; pad_len];
+
+    for marker in MARKERS {
+        input.extend(vec![marker; 2 * block_size]);
+    }
+
+    input
+}
+
+// only aligned marker blocks produce two different pairs of identical blocks next to each other
+// at least one filler byte precedes the markers, so a prefix ending in marker bytes can't fake the alignment
+fn find_marker_blocks(blocks: &[&[u8]]) -> Option<usize> {
+    (0..blocks.len().saturating_sub(3)).find(|i| {
+        blocks[*i] == blocks[*i + 1] && blocks[*i + 2] == blocks[*i + 3] && blocks[*i] != blocks[*i + 2]
+    })
+}
+
+// returns the number of filler bytes, the prefix length and the encrypted marker blocks
+fn align_marker(
+    oracle: &impl Fn(&[u8]) -> Vec<u8>,
+    block_size: usize,
+) -> Result<(usize, usize, Vec<Vec<u8>>), AttackError> {
+    for attempt in 0..MAX_ALIGNMENT_ATTEMPTS {
+        let pad_len = 1 + attempt % block_size;
+        let ciphertext = oracle(&marker_input(pad_len, block_size));
+        let blocks = blocks(&ciphertext, block_size);
+
+        if let Some(i) = find_marker_blocks(&blocks) {
+            let marker_blocks = vec![blocks[i].to_vec(), blocks[i + 2].to_vec()];
+
+            return Ok((pad_len, i * block_size - pad_len, marker_blocks));
+        }
+    }
+
+    Err(AttackError::UnknownPrefix)
+}
+
+pub fn find_prefix_len(oracle: impl Fn(&[u8]) -> Vec<u8>, block_size: usize) -> Result<usize, AttackError> {
+    let (_, prefix_len, _) = align_marker(&oracle, block_size)?;
+
+    Ok(prefix_len)
+}
+
+// turns an oracle of the form ECB(prefix || input || suffix) into ECB(input || suffix) by sending
+// the marker blocks in front of the input and dropping everything up to them
+// a query is repeated with shifted filler until the marker blocks are aligned, so a per-call random prefix works too
+fn strip_prefix(
+    oracle: impl Fn(&[u8]) -> Vec<u8>,
+    block_size: usize,
+    pad_len: usize,
+    marker_blocks: Vec<Vec<u8>>,
+) -> impl Fn(&[u8]) -> Result<Vec<u8>, AttackError> {
+    move |input: &[u8]| {
+        for attempt in 0..MAX_ALIGNMENT_ATTEMPTS {
+            let mut full_input = marker_input(1 + (pad_len - 1 + attempt) % block_size, block_size);
+            full_input.extend_from_slice(input);
+
+            let ciphertext = oracle(&full_input);
+            let blocks = blocks(&ciphertext, block_size);
+
+            if let Some(i) = find_marker_blocks(&blocks)
+                && blocks[i] == marker_blocks[0]
+                && blocks[i + 2] == marker_blocks[1] {
+                return Ok(ciphertext[(i + 4) * block_size..].to_vec());
+            }
+        }
+
+        Err(AttackError::UnknownPrefix)
+    }
+}
+
+pub fn crack_byte_at_a_time(oracle: impl Fn(&[u8]) -> Vec<u8>) -> Result<RecoveredSuffix, AttackError> {
+    let oracle_queries = Cell::new(0);
+    let counting_oracle = |input: &[u8]| {
+        oracle_queries.set(oracle_queries.get() + 1);
+        oracle(input)
+    };
+
+    let block_size = find_block_size(counting_oracle)?;
+
+    if !is_ecb_oracle(counting_oracle, block_size) {
+        return Err(AttackError::NotEcb);
+    }
+
+    let plaintext = recover_suffix(|input| Ok(counting_oracle(input)), block_size)?;
+
+    Ok(RecoveredSuffix { plaintext, block_size, oracle_queries: oracle_queries.get() })
+}
+
+pub fn crack_byte_at_a_time_with_prefix(oracle: impl Fn(&[u8]) -> Vec<u8>) -> Result<RecoveredSuffix, AttackError> {
+    let oracle_queries = Cell::new(0);
+    let counting_oracle = |input: &[u8]| {
+        oracle_queries.set(oracle_queries.get() + 1);
+        oracle(input)
+    };
+
+    let block_size = find_block_size(counting_oracle)?;
+
+    if !is_ecb_oracle(counting_oracle, block_size) {
+        return Err(AttackError::NotEcb);
+    }
+
+    let (pad_len, _, marker_blocks) = align_marker(&counting_oracle, block_size)?;
+    let plaintext = recover_suffix(strip_prefix(counting_oracle, block_size, pad_len, marker_blocks), block_size)?;
+
+    Ok(RecoveredSuffix { plaintext, block_size, oracle_queries: oracle_queries.get() })
+}
+
+#[cfg(test)]
+mod test_byte_at_a_time {
+    use set1::base64;
+
+    use super::*;
+
+    fn challenge_suffix() -> Vec<u8> {
+        let base64_input = std::fs::read_to_string("input/12.txt").unwrap().replace("\n", "");
+
+        base64::decode(base64_input.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn test_find_block_size() {
+        let oracle = ecb_suffix_oracle("secret".as_bytes().to_vec());
+
+        assert_eq!(Ok(BLOCK_SIZE), find_block_size(oracle));
+    }
+
+    #[test]
+    fn test_find_suffix_len() {
+        for len in 0..=2 * BLOCK_SIZE {
+            let oracle = ecb_suffix_oracle(vec![b'x'; len]);
+
+            assert_eq!(Ok(len), find_suffix_len(&|input: &[u8]| Ok(oracle(input)), BLOCK_SIZE));
+        }
+    }
+
+    #[test]
+    fn test_error_for_cbc_oracle() {
+        let key: [u8; BLOCK_SIZE] = rand::rng().random();
+        let oracle = |input: &[u8]| aes::encrypt(input, &key, Mode::CBC { iv: [0u8; BLOCK_SIZE] }).unwrap();
+
+        assert_eq!(AttackError::NotEcb, crack_byte_at_a_time(oracle).err().unwrap());
+    }
+
+    #[test]
+    fn test_crack_byte_at_a_time() {
+        let suffix = challenge_suffix();
+        let oracle = ecb_suffix_oracle(suffix.clone());
+
+        let recovered = crack_byte_at_a_time(oracle).unwrap();
+
+        assert_eq!(suffix, recovered.plaintext);
+        assert_eq!(BLOCK_SIZE, recovered.block_size);
+        assert!(recovered.oracle_queries > suffix.len());
+        assert!(recovered.oracle_queries <= 257 * suffix.len() + BLOCK_SIZE + 67);
+    }
+
+    #[test]
+    fn test_find_prefix_len() {
+        for prefix_len in 0..=3 * BLOCK_SIZE {
+            let key: [u8; BLOCK_SIZE] = rand::rng().random();
+            // prefix made of marker bytes must not confuse the alignment
+            let prefix = vec![MARKERS[0]; prefix_len];
+            let oracle = |input: &[u8]| {
+                let mut plaintext = prefix.clone();
+                plaintext.extend_from_slice(input);
+                plaintext.extend_from_slice(&[b'x'; 2 * BLOCK_SIZE]);
+
+                aes::encrypt(&plaintext, &key, Mode::ECB).unwrap()
+            };
+
+            assert_eq!(Ok(prefix_len), find_prefix_len(oracle, BLOCK_SIZE));
+        }
+    }
+
+    #[test]
+    fn test_strip_prefix_gives_up_on_lost_markers() {
+        let oracle = ecb_prefix_suffix_oracle("secret".as_bytes().to_vec(), 10, false);
+        // marker blocks from an earlier key never show up again, like after a key rotation
+        let stale_marker_blocks = vec![vec![0u8; BLOCK_SIZE], vec![1u8; BLOCK_SIZE]];
+
+        let stripped_oracle = strip_prefix(oracle, BLOCK_SIZE, 1, stale_marker_blocks);
+
+        assert_eq!(Err(AttackError::UnknownPrefix), stripped_oracle(b"input"));
+    }
+
+    #[test]
+    fn test_find_block_size_with_random_prefix() {
+        let oracle = ecb_prefix_suffix_oracle("secret".as_bytes().to_vec(), 100, true);
+
+        assert_eq!(Ok(BLOCK_SIZE), find_block_size(oracle));
+    }
+
+    #[test]
+    fn test_crack_byte_at_a_time_with_fixed_prefix() {
+        let suffix = challenge_suffix();
+
+        for _ in 0..5 {
+            let oracle = ecb_prefix_suffix_oracle(suffix.clone(), 50, false);
+
+            let recovered = crack_byte_at_a_time_with_prefix(oracle).unwrap();
+
+            assert_eq!(suffix, recovered.plaintext);
+        }
+    }
+
+    #[test]
+    fn test_crack_byte_at_a_time_with_random_prefix_per_call() {
+        let suffix = "Did you stop? No, I just drove by".as_bytes().to_vec();
+        let oracle = ecb_prefix_suffix_oracle(suffix.clone(), 50, true);
+
+        let recovered = crack_byte_at_a_time_with_prefix(oracle).unwrap();
+
+        assert_eq!(suffix, recovered.plaintext);
+        assert!(recovered.oracle_queries > 256 * suffix.len() / 2);
+    }
+}
@@ -0,0 +1,109 @@
+use rand::Rng;
+use set1::aes::{self, AesError, BLOCK_SIZE, Mode};
+use thiserror::Error;
+
+use crate::kv::{self, KvError};
+use crate::pkcs7;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ProfileError {
+    #[error(transparent)]
+    Aes(#[from] AesError),
+
+    #[error(transparent)]
+    Kv(#[from] KvError),
+
+    #[error("profile is not valid UTF-8")]
+    InvalidUtf8,
+}
+
+// hands out ECB encrypted profiles under a key only the service knows
+pub struct ProfileService {
+    key: [u8; BLOCK_SIZE],
+}
+
+impl ProfileService {
+    pub fn new() -> Self {
+        ProfileService { key: rand::rng().random() }
+    }
+
+    pub fn encrypted_profile_for(&self, email: &str) -> Vec<u8> {
+        aes::encrypt(kv::profile_for(email).as_bytes(), &self.key, Mode::ECB).unwrap()
+    }
+
+    pub fn decrypt_profile(&self, ciphertext: &[u8]) -> Result<Vec<(String, String)>, ProfileError> {
+        let plaintext = aes::decrypt(ciphertext, &self.key, Mode::ECB)?;
+        let profile = String::from_utf8(plaintext).map_err(|_| ProfileError::InvalidUtf8)?;
+
+        Ok(kv::parse(&profile)?)
+    }
+}
+
+impl Default for ProfileService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// builds a ciphertext of a role=admin profile using only encrypted profiles from the oracle
+pub fn forge_admin_profile(oracle: impl Fn(&str) -> Vec<u8>) -> Vec<u8> {
+    // "email=" is followed by enough filler to put a padded "admin" block at the start of the second block
+    let mut admin_block = "admin".as_bytes().to_vec();
+    pkcs7::pad(&mut admin_block, BLOCK_SIZE);
+
+    let mut email = "A".repeat(BLOCK_SIZE - "email=".len());
+    email.push_str(&String::from_utf8(admin_block).unwrap());
+
+    let encrypted_admin_block = oracle(&email)[BLOCK_SIZE..2 * BLOCK_SIZE].to_vec();
+
+    // the ciphertext grows by a block once the profile fills up its last block - four more email
+    // bytes push "user" into a block of its own, which is swapped for the admin block
+    let base_len = oracle("").len();
+    let filler_len = (1..=BLOCK_SIZE)
+        .find(|len| oracle(&"A".repeat(*len)).len() > base_len)
+        .unwrap();
+
+    let ciphertext = oracle(&"A".repeat(filler_len + "user".len()));
+    let mut forged_ciphertext = ciphertext[..ciphertext.len() - BLOCK_SIZE].to_vec();
+    forged_ciphertext.extend(encrypted_admin_block);
+
+    forged_ciphertext
+}
+
+#[cfg(test)]
+mod test_cut_and_paste {
+    use super::*;
+
+    fn role(profile: &[(String, String)]) -> Option<&str> {
+        profile.iter().find(|(key, _)| key == "role").map(|(_, value)| value.as_str())
+    }
+
+    #[test]
+    fn test_profile_service_round_trip() {
+        let service = ProfileService::new();
+
+        let profile = service.decrypt_profile(&service.encrypted_profile_for("foo@bar.com")).unwrap();
+
+        assert_eq!(kv::parse("email=foo@bar.com&uid=10&role=user").unwrap(), profile);
+    }
+
+    #[test]
+    fn test_profile_service_rejects_injected_role() {
+        let service = ProfileService::new();
+
+        let profile = service.decrypt_profile(&service.encrypted_profile_for("foo@bar.com&role=admin")).unwrap();
+
+        assert_eq!(Some("user"), role(&profile));
+    }
+
+    #[test]
+    fn test_forge_admin_profile() {
+        let service = ProfileService::new();
+
+        let forged_ciphertext = forge_admin_profile(|email| service.encrypted_profile_for(email));
+        let profile = service.decrypt_profile(&forged_ciphertext).unwrap();
+
+        assert_eq!(Some("admin"), role(&profile));
+        assert_eq!(3, profile.len());
+    }
+}
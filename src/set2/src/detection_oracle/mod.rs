@@ -0,0 +1,89 @@
+use rand::Rng;
+use set1::aes::{self, AesError, BLOCK_SIZE, Mode};
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DetectedMode {
+    ECB,
+    CBC,
+}
+
+// encrypts the input surrounded by 5-10 random bytes on each side under a random key,
+// randomly choosing between ECB and CBC (with a random iv)
+// returns the ciphertext together with the mode used so the detection can be verified
+pub fn encryption_oracle(input: &[u8]) -> Result<(Vec<u8>, DetectedMode), AesError> {
+    let mut rng = rand::rng();
+    let key: [u8; BLOCK_SIZE] = rng.random();
+
+    let mut plaintext = random_bytes(&mut rng, 5..=10);
+    plaintext.extend_from_slice(input);
+    plaintext.extend(random_bytes(&mut rng, 5..=10));
+
+    if rng.random() {
+        Ok((aes::encrypt(&plaintext, &key, Mode::ECB)?, DetectedMode::ECB))
+    } else {
+        let iv: [u8; BLOCK_SIZE] = rng.random();
+
+        Ok((aes::encrypt(&plaintext, &key, Mode::CBC { iv })?, DetectedMode::CBC))
+    }
+}
+
+fn random_bytes(rng: &mut impl Rng, len_range: std::ops::RangeInclusive<usize>) -> Vec<u8> {
+    let len = rng.random_range(len_range);
+
+    (0..len).map(|_| rng.random()).collect()
+}
+
+// three blocks of identical bytes guarantee two identical plaintext blocks regardless of the
+// random prefix, which ECB turns into two identical ciphertext blocks
+pub fn detect_mode(oracle: impl Fn(&[u8]) -> Vec<u8>) -> DetectedMode {
+    let ciphertext = oracle(&[b'A'; 3 * BLOCK_SIZE]);
+
+    if aes::is_ecb_encrypted(&ciphertext) {
+        DetectedMode::ECB
+    } else {
+        DetectedMode::CBC
+    }
+}
+
+#[cfg(test)]
+mod test_detection_oracle {
+    use std::cell::Cell;
+
+    use super::*;
+
+    #[test]
+    fn test_encryption_oracle_adds_prefix_and_suffix() {
+        for _ in 0..100 {
+            let (ciphertext, _) = encryption_oracle(&[0u8; BLOCK_SIZE]).unwrap();
+
+            // 16 input bytes + 10..=20 random bytes, padded to whole blocks
+            assert!(ciphertext.len() == 2 * BLOCK_SIZE || ciphertext.len() == 3 * BLOCK_SIZE);
+        }
+    }
+
+    #[test]
+    fn test_detect_mode_accuracy() {
+        let mut ecb_count = 0;
+
+        for _ in 0..5000 {
+            let used_mode = Cell::new(None);
+            let oracle = |input: &[u8]| {
+                let (ciphertext, mode) = encryption_oracle(input).unwrap();
+                used_mode.set(Some(mode));
+
+                ciphertext
+            };
+
+            let detected_mode = detect_mode(oracle);
+
+            assert_eq!(used_mode.get().unwrap(), detected_mode);
+
+            if detected_mode == DetectedMode::ECB {
+                ecb_count += 1;
+            }
+        }
+
+        // both modes have to be exercised
+        assert!(ecb_count > 0 && ecb_count < 5000);
+    }
+}
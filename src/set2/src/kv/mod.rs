@@ -0,0 +1,86 @@
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum KvError {
+    #[error("pair {pair:?} is missing '='")]
+    MissingSeparator {
+        pair: String,
+    },
+}
+
+// parses foo=bar&baz=qux into key-value pairs, keeping their order
+pub fn parse(input: &str) -> Result<Vec<(String, String)>, KvError> {
+    if input.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    input.split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => Ok((key.to_string(), value.to_string())),
+            None => Err(KvError::MissingSeparator { pair: pair.to_string() }),
+        })
+        .collect()
+}
+
+pub fn encode(pairs: &[(String, String)]) -> String {
+    pairs.iter()
+        .map(|(key, value)| format!("{key}={value}"))
+        .collect::<Vec<String>>()
+        .join("&")
+}
+
+// metacharacters are stripped from the email so it can't inject extra pairs
+pub fn profile_for(email: &str) -> String {
+    let email = email.replace(['&', '='], "");
+
+    encode(&[
+        ("email".to_string(), email),
+        ("uid".to_string(), "10".to_string()),
+        ("role".to_string(), "user".to_string()),
+    ])
+}
+
+#[cfg(test)]
+mod test_kv {
+    use super::*;
+
+    fn pairs(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(key, value)| (key.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn test_parse() {
+        let parsed = parse("foo=bar&baz=qux&zap=zazzle").unwrap();
+
+        assert_eq!(pairs(&[("foo", "bar"), ("baz", "qux"), ("zap", "zazzle")]), parsed);
+    }
+
+    #[test]
+    fn test_parse_empty_input() {
+        assert!(parse("").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_parse_error_for_missing_separator() {
+        let result = parse("foo=bar&baz");
+
+        assert_eq!(KvError::MissingSeparator { pair: "baz".to_string() }, result.err().unwrap());
+    }
+
+    #[test]
+    fn test_encode() {
+        let encoded = encode(&pairs(&[("foo", "bar"), ("baz", "qux")]));
+
+        assert_eq!("foo=bar&baz=qux", encoded);
+    }
+
+    #[test]
+    fn test_profile_for() {
+        assert_eq!("email=foo@bar.com&uid=10&role=user", profile_for("foo@bar.com"));
+    }
+
+    #[test]
+    fn test_profile_for_strips_metacharacters() {
+        assert_eq!("email=foo@bar.comroleadmin&uid=10&role=user", profile_for("foo@bar.com&role=admin"));
+    }
+}
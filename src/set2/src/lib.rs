@@ -0,0 +1,6 @@
+pub mod bitflipping;
+pub mod byte_at_a_time;
+pub mod cut_and_paste;
+pub mod detection_oracle;
+pub mod kv;
+pub mod pkcs7;
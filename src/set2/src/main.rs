@@ -1,4 +1,5 @@
-mod pkcs7;
+use set1::base64;
+use set2::{bitflipping, byte_at_a_time, cut_and_paste, detection_oracle, pkcs7};
 
 fn main() {
     // Challenge 1
@@ -7,4 +8,47 @@ fn main() {
     pkcs7::pad(&mut padded_input, 20);
 
     println!("{:?}", padded_input);
+
+    // Challenge 3 - ECB/CBC detection oracle
+    let oracle = |input: &[u8]| detection_oracle::encryption_oracle(input).unwrap().0;
+    let detected_mode = detection_oracle::detect_mode(oracle);
+
+    println!("{:?}", detected_mode);
+
+    // Challenge 4 - byte-at-a-time ECB decryption (simple)
+    let base64_input = std::fs::read_to_string("input/12.txt").unwrap().replace("\n", "");
+    let suffix = base64::decode(base64_input.as_bytes()).unwrap();
+    let oracle = byte_at_a_time::ecb_suffix_oracle(suffix.clone());
+    let recovered = byte_at_a_time::crack_byte_at_a_time(oracle).unwrap();
+
+    println!("{}", String::from_utf8_lossy(&recovered.plaintext));
+    println!("oracle queries: {}", recovered.oracle_queries);
+
+    // Challenge 5 - ECB cut-and-paste
+    let service = cut_and_paste::ProfileService::new();
+    let forged_ciphertext = cut_and_paste::forge_admin_profile(|email| service.encrypted_profile_for(email));
+    let profile = service.decrypt_profile(&forged_ciphertext).unwrap();
+
+    println!("{:?}", profile);
+
+    // Challenge 6 - byte-at-a-time ECB decryption (harder)
+    let oracle = byte_at_a_time::ecb_prefix_suffix_oracle(suffix, 50, false);
+    let recovered = byte_at_a_time::crack_byte_at_a_time_with_prefix(oracle).unwrap();
+
+    println!("{}", String::from_utf8_lossy(&recovered.plaintext));
+    println!("oracle queries: {}", recovered.oracle_queries);
+
+    // Challenge 7 - PKCS#7 padding validation
+    for input in ["ICE ICE BABY\x04\x04\x04\x04", "ICE ICE BABY\x05\x05\x05\x05", "ICE ICE BABY\x01\x02\x03\x04"] {
+        match pkcs7::unpad(input.as_bytes(), 16) {
+            Ok(unpadded_input) => println!("{}", String::from_utf8_lossy(unpadded_input)),
+            Err(err) => println!("{err}"),
+        }
+    }
+
+    // Challenge 8 - CBC bitflipping attacks
+    let service = bitflipping::CbcCommentService::new();
+    let forged_ciphertext = bitflipping::forge_admin(|userdata| service.encrypt_userdata(userdata));
+
+    println!("admin: {}", service.is_admin(&forged_ciphertext).unwrap());
 }
@@ -1,27 +1,62 @@
-pub fn pad(input: &mut Vec<u8>, block_size: usize) {
-    if input.len() % block_size == 0 {
-        return;
-    }
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PaddingError {
+    #[error("input length has to be a non-zero multiple of the block size")]
+    InvalidLength,
+
+    #[error("invalid padding length {pad_len}")]
+    InvalidPadLength {
+        pad_len: u8,
+    },
+
+    #[error("inconsistent padding bytes")]
+    InconsistentPadding,
+}
 
+// a block-aligned input gets a whole block of padding so the padding can always be removed unambiguously
+pub fn pad(input: &mut Vec<u8>, block_size: usize) {
     let pad_len = block_size - (input.len() % block_size);
     let padding = vec![pad_len as u8; pad_len];
 
     input.extend(padding.iter());
 }
 
+pub fn unpad(input: &[u8], block_size: usize) -> Result<&[u8], PaddingError> {
+    if input.is_empty() || !input.len().is_multiple_of(block_size) {
+        return Err(PaddingError::InvalidLength);
+    }
+
+    let pad_len = input[input.len() - 1];
+
+    if pad_len == 0 || pad_len as usize > block_size {
+        return Err(PaddingError::InvalidPadLength { pad_len });
+    }
+
+    let (unpadded_input, padding) = input.split_at(input.len() - pad_len as usize);
+
+    if padding.iter().any(|byte| *byte != pad_len) {
+        return Err(PaddingError::InconsistentPadding);
+    }
+
+    Ok(unpadded_input)
+}
+
 #[cfg(test)]
 mod test_pkcs7 {
     use super::*;
 
     #[test]
-    fn test_pad_no_extra_chars() {
+    fn test_pad_full_block_when_aligned() {
         let original_input = "abcdefghijkl";
+        let mut expected_input = original_input.as_bytes().to_vec();
+        expected_input.extend_from_slice(&[0x4, 0x4, 0x4, 0x4]);
         let mut input = original_input.as_bytes().to_vec();
         let block_size = 4;
 
         pad(&mut input, block_size);
 
-        assert_eq!(input, original_input.as_bytes());
+        assert_eq!(input, expected_input);
     }
 
     #[test]
@@ -36,4 +71,49 @@ mod test_pkcs7 {
 
         assert_eq!(input, expected_input);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_unpad_valid_padding() {
+        let input = "ICE ICE BABY\x04\x04\x04\x04".as_bytes();
+
+        assert_eq!(Ok("ICE ICE BABY".as_bytes()), unpad(input, 16));
+    }
+
+    #[test]
+    fn test_unpad_full_block_padding() {
+        let mut input = "YELLOW SUBMARINE".as_bytes().to_vec();
+        input.extend_from_slice(&[0x10; 16]);
+
+        assert_eq!(Ok("YELLOW SUBMARINE".as_bytes()), unpad(&input, 16));
+    }
+
+    #[test]
+    fn test_unpad_error_for_inconsistent_padding() {
+        assert_eq!(Err(PaddingError::InconsistentPadding), unpad("ICE ICE BABY\x05\x05\x05\x05".as_bytes(), 16));
+        assert_eq!(Err(PaddingError::InconsistentPadding), unpad("ICE ICE BABY\x01\x02\x03\x04".as_bytes(), 16));
+    }
+
+    #[test]
+    fn test_unpad_error_for_invalid_pad_length() {
+        assert_eq!(Err(PaddingError::InvalidPadLength { pad_len: 0 }), unpad("ICE ICE BABY\x04\x04\x04\x00".as_bytes(), 16));
+        assert_eq!(Err(PaddingError::InvalidPadLength { pad_len: 17 }), unpad(&[17u8; 16], 16));
+    }
+
+    #[test]
+    fn test_unpad_error_for_invalid_length() {
+        assert_eq!(Err(PaddingError::InvalidLength), unpad(&[], 16));
+        assert_eq!(Err(PaddingError::InvalidLength), unpad("ICE ICE BABY\x04\x04\x04".as_bytes(), 16));
+    }
+
+    #[test]
+    fn test_pad_unpad_round_trip() {
+        for len in 0..=40 {
+            let original_input = vec![0xaa; len];
+            let mut input = original_input.clone();
+
+            pad(&mut input, 16);
+
+            assert_eq!(Ok(original_input.as_slice()), unpad(&input, 16));
+        }
+    }
+}
@@ -0,0 +1,184 @@
+use rand::Rng;
+use set1::aes::{self, BLOCK_SIZE, CtrNonce, Mode};
+use set1::base64;
+use set1::crack_xor::{guess_key, guess_repeating_key};
+use set1::xor::xor;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RefinementError {
+    #[error("known plaintext at {position} doesn't fit ciphertext {index}")]
+    OutOfRange {
+        index: usize,
+        position: usize,
+    },
+}
+
+// encrypts every message under the same random key and a nonce of 0, so they all share one keystream
+pub fn encrypt_with_fixed_nonce(messages: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    let key: [u8; BLOCK_SIZE] = rand::rng().random();
+    let nonce = CtrNonce::LittleEndian64 { nonce: 0 };
+
+    messages.iter()
+        .map(|message| aes::encrypt(message, &key, Mode::CTR { nonce }).unwrap())
+        .collect()
+}
+
+pub fn decode_base64_lines(input: &str) -> Vec<Vec<u8>> {
+    input.lines()
+        .map(|line| base64::decode(line.as_bytes()).unwrap())
+        .collect()
+}
+
+// truncated to the shortest ciphertext, the ciphertexts are a repeating-key xor with the keystream
+// as the key, so every keystream byte can be guessed from its column alone
+pub fn recover_keystream(ciphertexts: &[Vec<u8>]) -> Vec<u8> {
+    let min_len = ciphertexts.iter().map(|ciphertext| ciphertext.len()).min().unwrap_or(0);
+
+    if min_len == 0 {
+        return Vec::new();
+    }
+
+    let concatenated_ciphertexts = ciphertexts.iter()
+        .flat_map(|ciphertext| ciphertext[..min_len].iter().copied())
+        .collect::<Vec<u8>>();
+
+    guess_repeating_key(&concatenated_ciphertexts, min_len)
+}
+
+// returns the plaintexts truncated to the length of the shortest one
+pub fn break_fixed_nonce_ctr(ciphertexts: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    let keystream = recover_keystream(ciphertexts);
+
+    ciphertexts.iter()
+        .map(|ciphertext| xor(&ciphertext[..keystream.len()], &keystream))
+        .collect()
+}
+
+// keystream guess covering the longest ciphertext that can be corrected by hand, one known
+// plaintext at a time, with every plaintext following the corrections
+pub struct KeystreamRefinement {
+    ciphertexts: Vec<Vec<u8>>,
+    keystream: Vec<u8>,
+}
+
+impl KeystreamRefinement {
+    // starts from a statistical guess of every column, however many ciphertexts reach it
+    pub fn new(ciphertexts: Vec<Vec<u8>>) -> Self {
+        let max_len = ciphertexts.iter().map(|ciphertext| ciphertext.len()).max().unwrap_or(0);
+        let keystream = (0..max_len)
+            .map(|position| {
+                let column = ciphertexts.iter()
+                    .filter_map(|ciphertext| ciphertext.get(position).copied())
+                    .collect::<Vec<u8>>();
+
+                guess_key(&column)
+            })
+            .collect();
+
+        KeystreamRefinement { ciphertexts, keystream }
+    }
+
+    pub fn keystream(&self) -> &[u8] {
+        &self.keystream
+    }
+
+    // fixes the keystream so that ciphertext `index` decrypts to `known` starting at `position`
+    pub fn pin(&mut self, index: usize, position: usize, known: &[u8]) -> Result<(), RefinementError> {
+        let ciphertext = self.ciphertexts.get(index)
+            .filter(|ciphertext| position + known.len() <= ciphertext.len())
+            .ok_or(RefinementError::OutOfRange { index, position })?;
+
+        let keystream_bytes = xor(&ciphertext[position..position + known.len()], known);
+        self.keystream[position..position + known.len()].copy_from_slice(&keystream_bytes);
+
+        Ok(())
+    }
+
+    pub fn plaintext(&self, index: usize) -> Vec<u8> {
+        let ciphertext = &self.ciphertexts[index];
+
+        xor(ciphertext, &self.keystream[..ciphertext.len()])
+    }
+
+    pub fn plaintexts(&self) -> Vec<Vec<u8>> {
+        (0..self.ciphertexts.len()).map(|index| self.plaintext(index)).collect()
+    }
+}
+
+#[cfg(test)]
+mod test_fixed_nonce_ctr {
+    use super::*;
+
+    fn challenge_messages() -> Vec<Vec<u8>> {
+        decode_base64_lines(&std::fs::read_to_string("input/19.txt").unwrap())
+    }
+
+    fn matching_bytes(x: &[u8], y: &[u8]) -> usize {
+        x.iter().zip(y).filter(|(x_byte, y_byte)| x_byte.eq_ignore_ascii_case(y_byte)).count()
+    }
+
+    #[test]
+    fn test_encrypt_with_fixed_nonce_shares_keystream() {
+        let messages = vec![vec![0u8; 20], vec![0u8; 30]];
+
+        let ciphertexts = encrypt_with_fixed_nonce(&messages);
+
+        assert_eq!(ciphertexts[0], ciphertexts[1][..20]);
+    }
+
+    #[test]
+    fn test_break_fixed_nonce_ctr() {
+        let messages = challenge_messages();
+        let ciphertexts = encrypt_with_fixed_nonce(&messages);
+
+        let plaintexts = break_fixed_nonce_ctr(&ciphertexts);
+        let min_len = messages.iter().map(|message| message.len()).min().unwrap();
+        let matching = messages.iter()
+            .zip(&plaintexts)
+            .map(|(message, plaintext)| matching_bytes(&message[..min_len], plaintext))
+            .sum::<usize>();
+
+        assert!(plaintexts.iter().all(|plaintext| plaintext.len() == min_len));
+        assert!(matching * 10 >= messages.len() * min_len * 9);
+    }
+
+    #[test]
+    fn test_recover_keystream_empty_ciphertext() {
+        assert!(recover_keystream(&[vec![1, 2, 3], Vec::new()]).is_empty());
+    }
+
+    #[test]
+    fn test_keystream_refinement_covers_longest_ciphertext() {
+        let messages = challenge_messages();
+        let max_len = messages.iter().map(|message| message.len()).max().unwrap();
+
+        let refinement = KeystreamRefinement::new(encrypt_with_fixed_nonce(&messages));
+
+        assert_eq!(max_len, refinement.keystream().len());
+        assert_eq!(messages[0].len(), refinement.plaintext(0).len());
+    }
+
+    #[test]
+    fn test_keystream_refinement_pin_updates_all_plaintexts() {
+        let messages = challenge_messages();
+        let mut refinement = KeystreamRefinement::new(encrypt_with_fixed_nonce(&messages));
+
+        // the longest line - "He, too, has been changed in his turn,"
+        let index = 37;
+        refinement.pin(index, 0, &messages[index]).unwrap();
+
+        for (message, plaintext) in messages.iter().zip(refinement.plaintexts()) {
+            assert_eq!(*message, plaintext);
+        }
+    }
+
+    #[test]
+    fn test_keystream_refinement_pin_error_for_out_of_range() {
+        let mut refinement = KeystreamRefinement::new(vec![vec![0u8; 10], vec![0u8; 20]]);
+
+        assert_eq!(Err(RefinementError::OutOfRange { index: 0, position: 5 }), refinement.pin(0, 5, b"too long"));
+        assert_eq!(Err(RefinementError::OutOfRange { index: 2, position: 0 }), refinement.pin(2, 0, b"x"));
+        assert_eq!(Ok(()), refinement.pin(1, 5, b"fits"));
+    }
+}
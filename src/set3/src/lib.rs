@@ -0,0 +1,6 @@
+pub mod fixed_nonce_ctr;
+pub mod mt_clone;
+pub mod mt_stream_cipher;
+pub mod padding_oracle;
+pub mod rng;
+pub mod seed_recovery;
@@ -0,0 +1,59 @@
+use rand::Rng;
+use set3::{fixed_nonce_ctr, mt_clone, mt_stream_cipher, padding_oracle, rng, seed_recovery};
+use set3::seed_recovery::Clock;
+
+fn main() {
+    // Challenge 1 - the CBC padding oracle
+    let input = std::fs::read_to_string("input/17.txt").unwrap();
+    let service = padding_oracle::PaddingOracleService::from_base64_lines(&input);
+    let (iv, ciphertext) = service.encrypt_random_message();
+    let oracle = |iv: &[u8], ciphertext: &[u8]| service.has_valid_padding(iv, ciphertext);
+    let plaintext = padding_oracle::padding_oracle_attack(oracle, &iv, &ciphertext).unwrap();
+
+    println!("{}", String::from_utf8_lossy(&plaintext));
+
+    // Challenge 3 - break fixed-nonce CTR mode using substitutions
+    let input = std::fs::read_to_string("input/19.txt").unwrap();
+    let ciphertexts = fixed_nonce_ctr::encrypt_with_fixed_nonce(&fixed_nonce_ctr::decode_base64_lines(&input));
+    let mut refinement = fixed_nonce_ctr::KeystreamRefinement::new(ciphertexts.clone());
+    refinement.pin(37, 0, "He, too, has been changed in his turn,".as_bytes()).unwrap();
+
+    for plaintext in refinement.plaintexts() {
+        println!("{}", String::from_utf8_lossy(&plaintext));
+    }
+
+    // Challenge 4 - break fixed-nonce CTR statistically
+    for plaintext in fixed_nonce_ctr::break_fixed_nonce_ctr(&ciphertexts) {
+        println!("{}", String::from_utf8_lossy(&plaintext));
+    }
+
+    // Challenge 5 - implement the MT19937 Mersenne Twister RNG
+    let mut mt = rng::Mt19937::new(rng::DEFAULT_SEED);
+
+    println!("{:?}", (0..5).map(|_| mt.next_u32()).collect::<Vec<u32>>());
+
+    // Challenge 6 - crack an MT19937 seed (simulating the passage of time)
+    let clock = seed_recovery::FakeClock::new(seed_recovery::SystemClock.now());
+    let output = seed_recovery::timestamp_seeded_output(&clock);
+    let recovered = seed_recovery::recover_timestamp_seed(output, &clock, 2000).unwrap();
+
+    println!("seed: {}, candidates tried: {}", recovered.seed, recovered.candidates_tried);
+
+    // Challenge 7 - clone an MT19937 RNG from its output
+    let mut mt = rng::Mt19937::new(recovered.seed);
+    let outputs: [u32; rng::STATE_SIZE] = std::array::from_fn(|_| mt.next_u32());
+    let mut cloned_mt = mt_clone::clone_from_outputs(&outputs);
+
+    println!("next output: {}, predicted: {}", mt.next_u32(), cloned_mt.next_u32());
+
+    // Challenge 8 - create the MT19937 stream cipher and break it
+    let known = [b'A'; 14];
+    let ciphertext = mt_stream_cipher::encrypt_with_random_prefix(&known, rand::rng().random());
+    let seed = mt_stream_cipher::recover_seed(&ciphertext, &known).unwrap();
+
+    println!("16-bit seed: {seed}");
+
+    let token = mt_stream_cipher::password_reset_token(&clock);
+
+    println!("time seeded token: {}", mt_stream_cipher::is_time_seeded_token(&token, &clock, 600));
+}
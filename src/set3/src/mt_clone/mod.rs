@@ -0,0 +1,73 @@
+use crate::rng::{Mt19937, STATE_SIZE};
+
+// inverts y ^ (y >> shift) - every pass recovers another `shift` bits from the top
+fn invert_right_shift_xor(y: u32, shift: u32) -> u32 {
+    let mut x = y;
+
+    for _ in 0..32 / shift {
+        x = y ^ (x >> shift);
+    }
+
+    x
+}
+
+// inverts y ^ ((y << shift) & mask) - every pass recovers another `shift` bits from the bottom
+fn invert_left_shift_xor(y: u32, shift: u32, mask: u32) -> u32 {
+    let mut x = y;
+
+    for _ in 0..32 / shift {
+        x = y ^ ((x << shift) & mask);
+    }
+
+    x
+}
+
+// inverse of the MT19937 output tempering, giving back the state word behind an output
+pub fn untemper(y: u32) -> u32 {
+    let y = invert_right_shift_xor(y, 18);
+    let y = invert_left_shift_xor(y, 15, 0xefc60000);
+    let y = invert_left_shift_xor(y, 7, 0x9d2c5680);
+
+    invert_right_shift_xor(y, 11)
+}
+
+// 624 consecutive outputs starting right after a twist untemper into the whole generator state
+pub fn clone_from_outputs(outputs: &[u32; STATE_SIZE]) -> Mt19937 {
+    Mt19937::from_state(outputs.map(untemper))
+}
+
+#[cfg(test)]
+mod test_mt_clone {
+    use rand::Rng;
+
+    use crate::rng::temper;
+
+    use super::*;
+
+    #[test]
+    fn test_untemper() {
+        let mut rng = rand::rng();
+
+        for y in [0, 1, 0x80000000, u32::MAX] {
+            assert_eq!(y, untemper(temper(y)));
+        }
+
+        for _ in 0..100000 {
+            let y: u32 = rng.random();
+
+            assert_eq!(y, untemper(temper(y)));
+        }
+    }
+
+    #[test]
+    fn test_clone_from_outputs() {
+        let mut original = Mt19937::new(rand::rng().random());
+        let outputs: [u32; STATE_SIZE] = std::array::from_fn(|_| original.next_u32());
+
+        let mut clone = clone_from_outputs(&outputs);
+
+        for _ in 0..2_000_000 {
+            assert_eq!(original.next_u32(), clone.next_u32());
+        }
+    }
+}
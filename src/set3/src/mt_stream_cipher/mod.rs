@@ -0,0 +1,107 @@
+use rand::Rng;
+use rand_core::RngCore;
+use set1::xor::xor;
+
+use crate::rng::Mt19937;
+use crate::seed_recovery::Clock;
+
+const TOKEN_LEN: usize = 16;
+
+// every generator output gives four little-endian keystream bytes
+fn keystream(seed: u32, len: usize) -> Vec<u8> {
+    let mut keystream = vec![0u8; len];
+    Mt19937::new(seed).fill_bytes(&mut keystream);
+
+    keystream
+}
+
+pub fn encrypt(input: &[u8], seed: u16) -> Vec<u8> {
+    xor(input, &keystream(seed as u32, input.len()))
+}
+
+pub fn decrypt(input: &[u8], seed: u16) -> Vec<u8> {
+    encrypt(input, seed)
+}
+
+// encrypts the known plaintext behind 5-50 random bytes
+pub fn encrypt_with_random_prefix(known: &[u8], seed: u16) -> Vec<u8> {
+    let mut rng = rand::rng();
+    let prefix_len = rng.random_range(5..=50);
+    let mut plaintext = (0..prefix_len).map(|_| rng.random()).collect::<Vec<u8>>();
+    plaintext.extend_from_slice(known);
+
+    encrypt(&plaintext, seed)
+}
+
+// a 16-bit seed is small enough to try every one until the plaintext ends with the known bytes
+pub fn recover_seed(ciphertext: &[u8], known_suffix: &[u8]) -> Option<u16> {
+    if known_suffix.is_empty() || known_suffix.len() > ciphertext.len() {
+        return None;
+    }
+
+    (0..=u16::MAX).find(|seed| decrypt(ciphertext, *seed).ends_with(known_suffix))
+}
+
+pub fn password_reset_token(clock: &impl Clock) -> Vec<u8> {
+    keystream(clock.now(), TOKEN_LEN)
+}
+
+// checks the token against the ones generated from every timestamp of the last `window_seconds`
+pub fn is_time_seeded_token(token: &[u8], clock: &impl Clock, window_seconds: u32) -> bool {
+    let now = clock.now();
+
+    (0..=window_seconds).any(|age| keystream(now.wrapping_sub(age), token.len()) == token)
+}
+
+#[cfg(test)]
+mod test_mt_stream_cipher {
+    use crate::seed_recovery::FakeClock;
+
+    use super::*;
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let plaintext = "YELLOW SUBMARINE, nineteen bytes".as_bytes();
+
+        let ciphertext = encrypt(plaintext, 0x1234);
+
+        assert_ne!(plaintext, ciphertext);
+        assert_eq!(plaintext, decrypt(&ciphertext, 0x1234));
+        assert_ne!(plaintext, decrypt(&ciphertext, 0x1235));
+    }
+
+    #[test]
+    fn test_recover_seed() {
+        let known = [b'A'; 14];
+
+        for seed in [0, 0xbeef, u16::MAX, rand::rng().random()] {
+            let ciphertext = encrypt_with_random_prefix(&known, seed);
+
+            assert_eq!(Some(seed), recover_seed(&ciphertext, &known));
+        }
+    }
+
+    #[test]
+    fn test_recover_seed_error_for_too_long_known_suffix() {
+        assert_eq!(None, recover_seed(&[0u8; 10], &[b'A'; 14]));
+    }
+
+    #[test]
+    fn test_is_time_seeded_token() {
+        let clock = FakeClock::new(1_700_000_000);
+        let token = password_reset_token(&clock);
+        clock.sleep(300);
+
+        assert_eq!(TOKEN_LEN, token.len());
+        assert!(is_time_seeded_token(&token, &clock, 600));
+        assert!(!is_time_seeded_token(&token, &clock, 200));
+    }
+
+    #[test]
+    fn test_is_time_seeded_token_false_for_random_token() {
+        let clock = FakeClock::new(1_700_000_000);
+        let token: [u8; TOKEN_LEN] = rand::rng().random();
+
+        assert!(!is_time_seeded_token(&token, &clock, 600));
+    }
+}
@@ -0,0 +1,203 @@
+use rand::Rng;
+use rand::seq::IndexedRandom;
+use set1::aes::{self, AesError, BLOCK_SIZE, Mode};
+use set1::base64;
+use set1::xor::xor;
+use set2::pkcs7::{self, PaddingError};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum PaddingOracleError {
+    #[error("ciphertext length has to be a non-zero multiple of the block size")]
+    InvalidLength,
+
+    #[error("no guess gives valid padding for byte {position} of block {block}")]
+    NoValidPadding {
+        block: usize,
+        position: usize,
+    },
+
+    #[error(transparent)]
+    Padding(#[from] PaddingError),
+}
+
+// encrypts one of the given messages under a random key and iv, and tells whether a ciphertext
+// decrypts to validly padded plaintext - without revealing anything else
+pub struct PaddingOracleService {
+    key: [u8; BLOCK_SIZE],
+    messages: Vec<Vec<u8>>,
+}
+
+impl PaddingOracleService {
+    pub fn new(messages: Vec<Vec<u8>>) -> Self {
+        PaddingOracleService { key: rand::rng().random(), messages }
+    }
+
+    pub fn from_base64_lines(input: &str) -> Self {
+        let messages = input.lines()
+            .map(|line| base64::decode(line.as_bytes()).unwrap())
+            .collect();
+
+        Self::new(messages)
+    }
+
+    // returns the iv and the ciphertext of a randomly chosen message
+    pub fn encrypt_random_message(&self) -> ([u8; BLOCK_SIZE], Vec<u8>) {
+        let mut rng = rand::rng();
+        let iv: [u8; BLOCK_SIZE] = rng.random();
+        let message = self.messages.choose(&mut rng).unwrap();
+
+        (iv, aes::encrypt(message, &self.key, Mode::CBC { iv }).unwrap())
+    }
+
+    pub fn has_valid_padding(&self, iv: &[u8], ciphertext: &[u8]) -> bool {
+        let Ok(iv) = iv.try_into() else {
+            return false;
+        };
+
+        !matches!(
+            aes::decrypt(ciphertext, &self.key, Mode::CBC { iv }),
+            Err(AesError::InvalidPadding) | Err(AesError::InvalidLength)
+        )
+    }
+}
+
+// recovers the block cipher decryption of the block, working from the last byte backwards
+// a forged previous block that makes the plaintext end in n bytes of n reveals one more byte
+fn decrypt_block(
+    oracle: &impl Fn(&[u8], &[u8]) -> bool,
+    previous_block: &[u8],
+    block: &[u8],
+    block_index: usize,
+) -> Result<Vec<u8>, PaddingOracleError> {
+    let mut intermediate = vec![0u8; BLOCK_SIZE];
+    let mut forged_block = previous_block.to_vec();
+
+    for position in (0..BLOCK_SIZE).rev() {
+        let pad_len = (BLOCK_SIZE - position) as u8;
+
+        for i in position + 1..BLOCK_SIZE {
+            forged_block[i] = intermediate[i] ^ pad_len;
+        }
+
+        let guess = (0..=255u8).find(|guess| {
+            forged_block[position] = *guess;
+
+            if !oracle(&forged_block, block) {
+                return false;
+            }
+
+            // for the last byte a valid padding might be longer than a single byte (e.g. \x02\x02)
+            // changing the byte before it only keeps the padding valid when it's \x01
+            if position == BLOCK_SIZE - 1 {
+                let mut check_block = forged_block.clone();
+                check_block[position - 1] ^= 1;
+
+                return oracle(&check_block, block);
+            }
+
+            true
+        });
+
+        match guess {
+            Some(guess) => intermediate[position] = guess ^ pad_len,
+            None => return Err(PaddingOracleError::NoValidPadding { block: block_index, position }),
+        }
+    }
+
+    Ok(xor(&intermediate, previous_block))
+}
+
+// recovers the plaintext of a CBC ciphertext with nothing but a padding oracle
+pub fn padding_oracle_attack(
+    oracle: impl Fn(&[u8], &[u8]) -> bool,
+    iv: &[u8],
+    ciphertext: &[u8],
+) -> Result<Vec<u8>, PaddingOracleError> {
+    if ciphertext.is_empty() || !ciphertext.len().is_multiple_of(BLOCK_SIZE) || iv.len() != BLOCK_SIZE {
+        return Err(PaddingOracleError::InvalidLength);
+    }
+
+    let mut padded_plaintext = Vec::with_capacity(ciphertext.len());
+    let mut previous_block = iv;
+
+    for (block_index, block) in ciphertext.chunks_exact(BLOCK_SIZE).enumerate() {
+        padded_plaintext.extend(decrypt_block(&oracle, previous_block, block, block_index)?);
+        previous_block = block;
+    }
+
+    Ok(pkcs7::unpad(&padded_plaintext, BLOCK_SIZE)?.to_vec())
+}
+
+#[cfg(test)]
+mod test_padding_oracle {
+    use super::*;
+
+    fn attack_message(message: &[u8]) -> Vec<u8> {
+        let service = PaddingOracleService::new(vec![message.to_vec()]);
+        let (iv, ciphertext) = service.encrypt_random_message();
+
+        padding_oracle_attack(|iv, ciphertext| service.has_valid_padding(iv, ciphertext), &iv, &ciphertext).unwrap()
+    }
+
+    #[test]
+    fn test_has_valid_padding() {
+        let service = PaddingOracleService::new(vec!["YELLOW SUBMARINE".as_bytes().to_vec()]);
+        let (mut iv, ciphertext) = service.encrypt_random_message();
+
+        assert!(service.has_valid_padding(&iv, &ciphertext));
+        assert!(!service.has_valid_padding(&iv, &ciphertext[..BLOCK_SIZE - 1]));
+
+        // the last block is a full block of padding - breaking it breaks the padding
+        let mut tampered_ciphertext = ciphertext.clone();
+        tampered_ciphertext[BLOCK_SIZE - 1] ^= 1;
+
+        assert!(!service.has_valid_padding(&iv, &tampered_ciphertext));
+
+        iv[0] ^= 1;
+
+        assert!(service.has_valid_padding(&iv, &ciphertext));
+    }
+
+    #[test]
+    fn test_padding_oracle_attack_challenge_messages() {
+        let input = std::fs::read_to_string("input/17.txt").unwrap();
+
+        for line in input.lines() {
+            let message = base64::decode(line.as_bytes()).unwrap();
+
+            assert_eq!(message, attack_message(&message));
+        }
+    }
+
+    #[test]
+    fn test_padding_oracle_attack_all_lengths() {
+        for len in 0..=3 * BLOCK_SIZE {
+            let message = vec![b'x'; len];
+
+            assert_eq!(message, attack_message(&message));
+        }
+    }
+
+    #[test]
+    fn test_padding_oracle_attack_ambiguous_last_byte() {
+        // the message leaves \x02\x02 padding, and the data itself ends in \x02 and \x03\x03\x03
+        for message in ["fourteen bytes", "fifteen bytes\x02\x02", "sixteen bytes\x03\x03\x03"] {
+            assert_eq!(message.as_bytes(), attack_message(message.as_bytes()));
+        }
+    }
+
+    #[test]
+    fn test_padding_oracle_attack_error_for_invalid_length() {
+        let result = padding_oracle_attack(|_, _| true, &[0u8; BLOCK_SIZE], &[0u8; BLOCK_SIZE + 1]);
+
+        assert_eq!(PaddingOracleError::InvalidLength, result.err().unwrap());
+    }
+
+    #[test]
+    fn test_padding_oracle_attack_error_for_useless_oracle() {
+        let result = padding_oracle_attack(|_, _| false, &[0u8; BLOCK_SIZE], &[0u8; BLOCK_SIZE]);
+
+        assert_eq!(PaddingOracleError::NoValidPadding { block: 0, position: BLOCK_SIZE - 1 }, result.err().unwrap());
+    }
+}
@@ -0,0 +1,266 @@
+use rand_core::{RngCore, SeedableRng, impls};
+
+pub const STATE_SIZE: usize = N;
+
+const N: usize = 624;
+const M: usize = 397;
+const MATRIX_A: u32 = 0x9908b0df;
+const UPPER_MASK: u32 = 0x80000000;
+const LOWER_MASK: u32 = 0x7fffffff;
+
+const N_64: usize = 312;
+const M_64: usize = 156;
+const MATRIX_A_64: u64 = 0xb5026f5aa96619e9;
+const UPPER_MASK_64: u64 = 0xffffffff80000000;
+const LOWER_MASK_64: u64 = 0x7fffffff;
+
+pub const DEFAULT_SEED: u32 = 5489;
+
+// 32-bit Mersenne Twister
+pub struct Mt19937 {
+    state: [u32; N],
+    index: usize,
+}
+
+impl Mt19937 {
+    pub fn new(seed: u32) -> Self {
+        let mut state = [0u32; N];
+        state[0] = seed;
+
+        for i in 1..N {
+            state[i] = 1812433253u32
+                .wrapping_mul(state[i - 1] ^ (state[i - 1] >> 30))
+                .wrapping_add(i as u32);
+        }
+
+        Mt19937 { state, index: N }
+    }
+
+    // generator that twists the given state before its next output
+    pub fn from_state(state: [u32; N]) -> Self {
+        Mt19937 { state, index: N }
+    }
+
+    fn twist(&mut self) {
+        for i in 0..N {
+            let y = (self.state[i] & UPPER_MASK) | (self.state[(i + 1) % N] & LOWER_MASK);
+            let mag = if y & 1 == 0 { 0 } else { MATRIX_A };
+
+            self.state[i] = self.state[(i + M) % N] ^ (y >> 1) ^ mag;
+        }
+
+        self.index = 0;
+    }
+
+    pub fn next_u32(&mut self) -> u32 {
+        if self.index >= N {
+            self.twist();
+        }
+
+        let y = self.state[self.index];
+        self.index += 1;
+
+        temper(y)
+    }
+}
+
+// the output function applied to every state word
+pub fn temper(y: u32) -> u32 {
+    let mut y = y;
+    y ^= y >> 11;
+    y ^= (y << 7) & 0x9d2c5680;
+    y ^= (y << 15) & 0xefc60000;
+    y ^= y >> 18;
+
+    y
+}
+
+impl Default for Mt19937 {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEED)
+    }
+}
+
+impl RngCore for Mt19937 {
+    fn next_u32(&mut self) -> u32 {
+        Mt19937::next_u32(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_u32(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+}
+
+impl SeedableRng for Mt19937 {
+    type Seed = [u8; 4];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u32::from_le_bytes(seed))
+    }
+
+    // seeds directly with the low 32 bits so seed_from_u64(5489) matches the reference generator
+    fn seed_from_u64(state: u64) -> Self {
+        Self::new(state as u32)
+    }
+}
+
+// 64-bit Mersenne Twister
+pub struct Mt19937_64 {
+    state: [u64; N_64],
+    index: usize,
+}
+
+impl Mt19937_64 {
+    pub fn new(seed: u64) -> Self {
+        let mut state = [0u64; N_64];
+        state[0] = seed;
+
+        for i in 1..N_64 {
+            state[i] = 6364136223846793005u64
+                .wrapping_mul(state[i - 1] ^ (state[i - 1] >> 62))
+                .wrapping_add(i as u64);
+        }
+
+        Mt19937_64 { state, index: N_64 }
+    }
+
+    fn twist(&mut self) {
+        for i in 0..N_64 {
+            let y = (self.state[i] & UPPER_MASK_64) | (self.state[(i + 1) % N_64] & LOWER_MASK_64);
+            let mag = if y & 1 == 0 { 0 } else { MATRIX_A_64 };
+
+            self.state[i] = self.state[(i + M_64) % N_64] ^ (y >> 1) ^ mag;
+        }
+
+        self.index = 0;
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        if self.index >= N_64 {
+            self.twist();
+        }
+
+        let mut y = self.state[self.index];
+        self.index += 1;
+
+        y ^= (y >> 29) & 0x5555555555555555;
+        y ^= (y << 17) & 0x71d67fffeda60000;
+        y ^= (y << 37) & 0xfff7eee000000000;
+        y ^= y >> 43;
+
+        y
+    }
+}
+
+impl Default for Mt19937_64 {
+    fn default() -> Self {
+        Self::new(DEFAULT_SEED as u64)
+    }
+}
+
+impl RngCore for Mt19937_64 {
+    fn next_u32(&mut self) -> u32 {
+        Mt19937_64::next_u64(self) as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        Mt19937_64::next_u64(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+}
+
+impl SeedableRng for Mt19937_64 {
+    type Seed = [u8; 8];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        Self::new(u64::from_le_bytes(seed))
+    }
+
+    fn seed_from_u64(state: u64) -> Self {
+        Self::new(state)
+    }
+}
+
+#[cfg(test)]
+mod test_rng {
+    use rand::Rng;
+
+    use super::*;
+
+    #[test]
+    fn test_mt19937_reference_output() {
+        let mut rng = Mt19937::new(DEFAULT_SEED);
+        let expected_outputs = [3499211612, 581869302, 3890346734, 3586334585, 545404204];
+
+        for expected_output in expected_outputs {
+            assert_eq!(expected_output, rng.next_u32());
+        }
+    }
+
+    #[test]
+    fn test_mt19937_reference_10000th_output() {
+        let mut rng = Mt19937::default();
+
+        let output = (0..10000).map(|_| rng.next_u32()).last().unwrap();
+
+        assert_eq!(4123659995, output);
+    }
+
+    #[test]
+    fn test_from_state_continues_after_twist() {
+        let mut rng = Mt19937::default();
+        let state = rng.state;
+        let mut rng_from_state = Mt19937::from_state(state);
+
+        assert_eq!(rng.next_u32(), rng_from_state.next_u32());
+    }
+
+    #[test]
+    fn test_mt19937_64_reference_output() {
+        let mut rng = Mt19937_64::default();
+
+        assert_eq!(14514284786278117030, rng.next_u64());
+
+        let output = (1..10000).map(|_| rng.next_u64()).last().unwrap();
+
+        assert_eq!(9981545732273789042, output);
+    }
+
+    #[test]
+    fn test_seedable_rng() {
+        let mut rng1 = Mt19937::seed_from_u64(DEFAULT_SEED as u64);
+        let mut rng2 = Mt19937::from_seed(DEFAULT_SEED.to_le_bytes());
+        let mut rng3 = Mt19937_64::seed_from_u64(DEFAULT_SEED as u64);
+
+        assert_eq!(3499211612, rng1.next_u32());
+        assert_eq!(3499211612, rng2.next_u32());
+        assert_eq!(14514284786278117030, rng3.next_u64());
+    }
+
+    #[test]
+    fn test_rng_core_fill_bytes() {
+        let mut rng = Mt19937::default();
+        let mut bytes = [0u8; 6];
+
+        rng.fill_bytes(&mut bytes);
+
+        assert_eq!(3499211612u32.to_le_bytes(), bytes[..4]);
+        assert_eq!(581869302u32.to_le_bytes()[..2], bytes[4..]);
+    }
+
+    #[test]
+    fn test_usable_with_rand() {
+        let mut rng = Mt19937::new(42);
+
+        let value = rng.random_range(10..20);
+
+        assert!((10..20).contains(&value));
+    }
+}
@@ -0,0 +1,122 @@
+use std::cell::Cell;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use rand::Rng;
+
+use crate::rng::Mt19937;
+
+// source of unix timestamps, so tests can simulate the passage of time instead of sleeping
+pub trait Clock {
+    fn now(&self) -> u32;
+
+    fn sleep(&self, seconds: u32);
+}
+
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u32 {
+        SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_secs() as u32
+    }
+
+    fn sleep(&self, seconds: u32) {
+        std::thread::sleep(Duration::from_secs(seconds as u64));
+    }
+}
+
+pub struct FakeClock {
+    now: Cell<u32>,
+}
+
+impl FakeClock {
+    pub fn new(now: u32) -> Self {
+        FakeClock { now: Cell::new(now) }
+    }
+}
+
+impl Clock for FakeClock {
+    fn now(&self) -> u32 {
+        self.now.get()
+    }
+
+    fn sleep(&self, seconds: u32) {
+        self.now.set(self.now.get() + seconds);
+    }
+}
+
+pub struct RecoveredSeed {
+    pub seed: u32,
+    pub candidates_tried: usize,
+}
+
+// waits 40-1000 seconds, seeds MT19937 with the current timestamp, waits again and returns the first output
+pub fn timestamp_seeded_output(clock: &impl Clock) -> u32 {
+    let mut rng = rand::rng();
+
+    clock.sleep(rng.random_range(40..=1000));
+    let output = Mt19937::new(clock.now()).next_u32();
+    clock.sleep(rng.random_range(40..=1000));
+
+    output
+}
+
+// re-seeds with every timestamp from now back to the start of the window until the first output matches
+pub fn recover_timestamp_seed(output: u32, clock: &impl Clock, window_seconds: u32) -> Option<RecoveredSeed> {
+    let now = clock.now();
+
+    (0..=window_seconds)
+        .map(|age| now.wrapping_sub(age))
+        .enumerate()
+        .find(|(_, seed)| Mt19937::new(*seed).next_u32() == output)
+        .map(|(i, seed)| RecoveredSeed { seed, candidates_tried: i + 1 })
+}
+
+#[cfg(test)]
+mod test_seed_recovery {
+    use super::*;
+
+    #[test]
+    fn test_fake_clock_sleep_advances_time() {
+        let clock = FakeClock::new(1_700_000_000);
+
+        clock.sleep(40);
+
+        assert_eq!(1_700_000_040, clock.now());
+    }
+
+    #[test]
+    fn test_recover_timestamp_seed() {
+        let clock = FakeClock::new(1_700_000_000);
+        let output = Mt19937::new(clock.now()).next_u32();
+        clock.sleep(100);
+
+        let recovered = recover_timestamp_seed(output, &clock, 2000).unwrap();
+
+        assert_eq!(1_700_000_000, recovered.seed);
+        assert_eq!(101, recovered.candidates_tried);
+    }
+
+    #[test]
+    fn test_recover_timestamp_seeded_output() {
+        let clock = FakeClock::new(1_700_000_000);
+
+        for _ in 0..10 {
+            let start = clock.now();
+            let output = timestamp_seeded_output(&clock);
+
+            let recovered = recover_timestamp_seed(output, &clock, 2000).unwrap();
+
+            assert!(recovered.seed >= start + 40 && recovered.seed <= start + 1000);
+            assert_eq!((clock.now() - recovered.seed + 1) as usize, recovered.candidates_tried);
+        }
+    }
+
+    #[test]
+    fn test_recover_timestamp_seed_outside_window() {
+        let clock = FakeClock::new(1_700_000_000);
+        let output = Mt19937::new(clock.now()).next_u32();
+        clock.sleep(3000);
+
+        assert!(recover_timestamp_seed(output, &clock, 2000).is_none());
+    }
+}
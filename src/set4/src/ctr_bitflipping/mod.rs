@@ -0,0 +1,92 @@
+use rand::Rng;
+use set1::aes::{self, AesError, BLOCK_SIZE, CtrNonce, Mode};
+use set1::xor::xor;
+use set2::bitflipping::{COMMENT_PREFIX, comment_for, is_admin};
+
+// the same comment service as the CBC one in set 2, only encrypting under CTR
+pub struct CtrCommentService {
+    key: [u8; BLOCK_SIZE],
+    nonce: CtrNonce,
+}
+
+impl CtrCommentService {
+    pub fn new() -> Self {
+        let mut rng = rand::rng();
+
+        CtrCommentService { key: rng.random(), nonce: CtrNonce::LittleEndian64 { nonce: rng.random() } }
+    }
+
+    pub fn encrypt_userdata(&self, userdata: &str) -> Vec<u8> {
+        aes::encrypt(comment_for(userdata).as_bytes(), &self.key, Mode::CTR { nonce: self.nonce }).unwrap()
+    }
+
+    pub fn is_admin(&self, ciphertext: &[u8]) -> Result<bool, AesError> {
+        let plaintext = aes::decrypt(ciphertext, &self.key, Mode::CTR { nonce: self.nonce })?;
+
+        Ok(is_admin(&plaintext))
+    }
+}
+
+impl Default for CtrCommentService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// a flipped ciphertext bit flips only the plaintext bit at the same position, so unlike CBC
+// there's no block to sacrifice and nothing else in the plaintext gets scrambled
+pub fn ctr_bitflip(ciphertext: &[u8], offset: usize, known: &[u8], desired: &[u8]) -> Vec<u8> {
+    let delta = xor(known, desired);
+    let mut flipped_ciphertext = ciphertext.to_vec();
+
+    for (byte, delta_byte) in flipped_ciphertext[offset..offset + delta.len()].iter_mut().zip(delta) {
+        *byte ^= delta_byte;
+    }
+
+    flipped_ciphertext
+}
+
+// the user data starts right after the prefix, so its placeholders get flipped in place
+pub fn forge_admin(oracle: impl Fn(&str) -> Vec<u8>) -> Vec<u8> {
+    let known = "XadminXtrueX";
+    let desired = ";admin=true;";
+    let ciphertext = oracle(known);
+
+    ctr_bitflip(&ciphertext, COMMENT_PREFIX.len(), known.as_bytes(), desired.as_bytes())
+}
+
+#[cfg(test)]
+mod test_ctr_bitflipping {
+    use super::*;
+
+    #[test]
+    fn test_service_not_admin_for_injected_userdata() {
+        let service = CtrCommentService::new();
+
+        let ciphertext = service.encrypt_userdata(";admin=true;");
+
+        assert_eq!(Ok(false), service.is_admin(&ciphertext));
+    }
+
+    #[test]
+    fn test_ctr_bitflip() {
+        let key = [0u8; BLOCK_SIZE];
+        let nonce = CtrNonce::LittleEndian64 { nonce: 0 };
+        let plaintext = "0123456789abcdef0123456789abcdef".as_bytes();
+        let ciphertext = aes::encrypt(plaintext, &key, Mode::CTR { nonce }).unwrap();
+
+        let flipped_ciphertext = ctr_bitflip(&ciphertext, 20, b"4567", b"wxyz");
+        let flipped_plaintext = aes::decrypt(&flipped_ciphertext, &key, Mode::CTR { nonce }).unwrap();
+
+        assert_eq!("0123456789abcdef0123wxyz89abcdef".as_bytes(), flipped_plaintext);
+    }
+
+    #[test]
+    fn test_forge_admin() {
+        let service = CtrCommentService::new();
+
+        let forged_ciphertext = forge_admin(|userdata| service.encrypt_userdata(userdata));
+
+        assert_eq!(Ok(true), service.is_admin(&forged_ciphertext));
+    }
+}
@@ -0,0 +1,142 @@
+use rand::Rng;
+use set1::aes::{self, AesError, BLOCK_SIZE, CtrNonce, Mode};
+use set1::xor::xor;
+use thiserror::Error;
+
+// the nonce used throughout cryptopals - zero, as a 64-bit little-endian value
+const NONCE: CtrNonce = CtrNonce::LittleEndian64 { nonce: 0 };
+
+#[derive(Error, Debug, PartialEq)]
+pub enum CtrEditError {
+    #[error(transparent)]
+    Aes(#[from] AesError),
+
+    #[error("offset {offset} is past the end of the {len} byte ciphertext")]
+    OffsetOutOfRange {
+        offset: usize,
+        len: usize,
+    },
+}
+
+// replaces the plaintext at the given offset with the new text - only the keystream under the
+// new text gets generated, and writing past the end extends the ciphertext
+pub fn ctr_edit(ciphertext: &[u8], key: &[u8], offset: usize, newtext: &[u8]) -> Result<Vec<u8>, CtrEditError> {
+    if offset > ciphertext.len() {
+        return Err(CtrEditError::OffsetOutOfRange { offset, len: ciphertext.len() });
+    }
+
+    let edited = aes::apply_ctr_at(newtext, key, &NONCE, offset)?;
+    let mut output = ciphertext.to_vec();
+    let overlap = (ciphertext.len() - offset).min(edited.len());
+    output[offset..offset + overlap].copy_from_slice(&edited[..overlap]);
+    output.extend_from_slice(&edited[overlap..]);
+
+    Ok(output)
+}
+
+// encrypts under a random key that never leaves the service, but lets anyone edit the ciphertext
+pub struct CtrEditService {
+    key: [u8; BLOCK_SIZE],
+}
+
+impl CtrEditService {
+    pub fn new() -> Self {
+        CtrEditService { key: rand::rng().random() }
+    }
+
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        aes::encrypt(plaintext, &self.key, Mode::CTR { nonce: NONCE }).unwrap()
+    }
+
+    pub fn edit(&self, ciphertext: &[u8], offset: usize, newtext: &[u8]) -> Result<Vec<u8>, CtrEditError> {
+        ctr_edit(ciphertext, &self.key, offset, newtext)
+    }
+}
+
+impl Default for CtrEditService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// writing zeros over the whole ciphertext makes the edit return the raw keystream
+pub fn recover_plaintext<F>(ciphertext: &[u8], edit: F) -> Result<Vec<u8>, CtrEditError>
+where
+    F: Fn(&[u8], usize, &[u8]) -> Result<Vec<u8>, CtrEditError>,
+{
+    let keystream = edit(ciphertext, 0, &vec![0u8; ciphertext.len()])?;
+
+    Ok(xor(ciphertext, &keystream))
+}
+
+#[cfg(test)]
+mod test_ctr_edit {
+    use set1::base64;
+
+    use super::*;
+
+    // challenge 7's plaintext, recovered from its ECB encryption
+    fn challenge_plaintext() -> Vec<u8> {
+        let base64_input = std::fs::read_to_string("input/25.txt").unwrap().replace("\n", "");
+        let input = base64::decode(base64_input.as_bytes()).unwrap();
+
+        aes::decrypt(&input, "YELLOW SUBMARINE".as_bytes(), Mode::ECB).unwrap()
+    }
+
+    #[test]
+    fn test_edit_replaces_plaintext_at_offset() {
+        let key = "YELLOW SUBMARINE".as_bytes();
+        let plaintext = b"the quick brown fox jumps over the lazy dog";
+        let ciphertext = aes::encrypt(plaintext, key, Mode::CTR { nonce: NONCE }).unwrap();
+
+        let edited = ctr_edit(&ciphertext, key, 20, b"leaps").unwrap();
+        let decrypted = aes::decrypt(&edited, key, Mode::CTR { nonce: NONCE }).unwrap();
+
+        assert_eq!(b"the quick brown fox leaps over the lazy dog".to_vec(), decrypted);
+    }
+
+    #[test]
+    fn test_edit_only_changes_edited_bytes() {
+        let key = "YELLOW SUBMARINE".as_bytes();
+        let ciphertext = aes::encrypt(&[7u8; 100], key, Mode::CTR { nonce: NONCE }).unwrap();
+
+        let edited = ctr_edit(&ciphertext, key, 33, b"abc").unwrap();
+
+        assert_eq!(ciphertext.len(), edited.len());
+        assert_eq!(ciphertext[..33], edited[..33]);
+        assert_eq!(ciphertext[36..], edited[36..]);
+        assert_ne!(ciphertext[33..36], edited[33..36]);
+    }
+
+    #[test]
+    fn test_edit_past_end_extends_ciphertext() {
+        let key = "YELLOW SUBMARINE".as_bytes();
+        let ciphertext = aes::encrypt(b"hello", key, Mode::CTR { nonce: NONCE }).unwrap();
+
+        let edited = ctr_edit(&ciphertext, key, 3, b"p me out").unwrap();
+        let decrypted = aes::decrypt(&edited, key, Mode::CTR { nonce: NONCE }).unwrap();
+
+        assert_eq!(b"help me out".to_vec(), decrypted);
+    }
+
+    #[test]
+    fn test_edit_offset_out_of_range() {
+        let key = "YELLOW SUBMARINE".as_bytes();
+        let ciphertext = aes::encrypt(b"hello", key, Mode::CTR { nonce: NONCE }).unwrap();
+
+        let result = ctr_edit(&ciphertext, key, 6, b"x");
+
+        assert_eq!(Err(CtrEditError::OffsetOutOfRange { offset: 6, len: 5 }), result);
+    }
+
+    #[test]
+    fn test_recover_plaintext() {
+        let plaintext = challenge_plaintext();
+        let service = CtrEditService::new();
+        let ciphertext = service.encrypt(&plaintext);
+
+        let recovered = recover_plaintext(&ciphertext, |ciphertext, offset, newtext| service.edit(ciphertext, offset, newtext)).unwrap();
+
+        assert_eq!(plaintext, recovered);
+    }
+}
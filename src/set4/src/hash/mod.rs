@@ -0,0 +1,29 @@
+// an incremental hash function
+pub trait Hash: Sized {
+    const BLOCK_SIZE: usize;
+    const DIGEST_SIZE: usize;
+
+    fn new() -> Self;
+
+    fn update(&mut self, input: &[u8]);
+
+    fn finalize(self) -> Vec<u8>;
+
+    fn digest(input: &[u8]) -> Vec<u8> {
+        let mut hash = Self::new();
+        hash.update(input);
+
+        hash.finalize()
+    }
+}
+
+// a Merkle-Damgard hash whose digest is its whole internal state, so hashing can be resumed
+// from any digest
+pub trait LengthExtension: Hash {
+    // the padding appended to a message of the given length
+    fn padding(message_len: u64) -> Vec<u8>;
+
+    // hasher resuming from the state encoded in a DIGEST_SIZE long digest, with `processed_len`
+    // counting everything hashed before it, padding included
+    fn from_digest(digest: &[u8], processed_len: u64) -> Self;
+}
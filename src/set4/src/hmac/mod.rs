@@ -0,0 +1,136 @@
+use crate::hash::Hash;
+
+const IPAD: u8 = 0x36;
+const OPAD: u8 = 0x5c;
+
+// HMAC as specified in RFC 2104 - H((K ^ opad) || H((K ^ ipad) || message)), with the key
+// hashed first if it's longer than a block and zero padded to a full block
+pub struct Hmac<H: Hash> {
+    inner: H,
+    outer_key: Vec<u8>,
+}
+
+impl<H: Hash> Hmac<H> {
+    pub fn new(key: &[u8]) -> Self {
+        let mut block_key = if key.len() > H::BLOCK_SIZE { H::digest(key) } else { key.to_vec() };
+        block_key.resize(H::BLOCK_SIZE, 0);
+
+        let inner_key = block_key.iter().map(|byte| byte ^ IPAD).collect::<Vec<u8>>();
+        let outer_key = block_key.iter().map(|byte| byte ^ OPAD).collect();
+
+        let mut inner = H::new();
+        inner.update(&inner_key);
+
+        Hmac { inner, outer_key }
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.inner.update(input);
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        let mut outer = H::new();
+        outer.update(&self.outer_key);
+        outer.update(&self.inner.finalize());
+
+        outer.finalize()
+    }
+}
+
+pub fn hmac<H: Hash>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut hmac = Hmac::<H>::new(key);
+    hmac.update(message);
+
+    hmac.finalize()
+}
+
+#[cfg(test)]
+mod test_hmac {
+    use set1::util::hex_to_string;
+
+    use crate::md4::{self, Md4};
+    use crate::sha1::Sha1;
+    use crate::sha256::Sha256;
+
+    use super::*;
+
+    // the RFC 2202 and RFC 4231 test cases share their keys and messages
+    fn test_cases(long_key_len: usize) -> Vec<(Vec<u8>, Vec<u8>)> {
+        vec![
+            (vec![0x0b; 20], b"Hi There".to_vec()),
+            (b"Jefe".to_vec(), b"what do ya want for nothing?".to_vec()),
+            (vec![0xaa; 20], vec![0xdd; 50]),
+            ((1..=25).collect(), vec![0xcd; 50]),
+            (vec![0xaa; long_key_len], b"Test Using Larger Than Block-Size Key - Hash Key First".to_vec()),
+        ]
+    }
+
+    #[test]
+    fn test_hmac_sha1_rfc_2202_vectors() {
+        let expected = [
+            "b617318655057264e28bc0b6fb378c8ef146be00",
+            "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79",
+            "125d7342b9ac11cd91a39af48aa17b4f63f175d3",
+            "4c9007f4026250c6bc8414f9bf50c86c2d7235da",
+            "aa4ae5e15272d00e95705637ce8a3b55ed402112",
+        ];
+
+        for ((key, message), expected) in test_cases(80).iter().zip(expected) {
+            assert_eq!(expected, hex_to_string(&hmac::<Sha1>(key, message)));
+        }
+
+        let message = b"Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data";
+
+        assert_eq!("e8e99d0f45237d786d6bbaa7965c7808bbff1a91", hex_to_string(&hmac::<Sha1>(&[0xaa; 80], message)));
+    }
+
+    #[test]
+    fn test_hmac_sha256_rfc_4231_vectors() {
+        let expected = [
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+            "82558a389a443c0ea4cc819899f2083a85f0faa3e578f8077a2e3ff46729665b",
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+        ];
+
+        for ((key, message), expected) in test_cases(131).iter().zip(expected) {
+            assert_eq!(expected, hex_to_string(&hmac::<Sha256>(key, message)));
+        }
+
+        let message = b"This is a test using a larger than block-size key and a larger than block-size data. \
+            The key needs to be hashed before being used by the HMAC algorithm.";
+
+        assert_eq!(
+            "9b09ffa71b942fcb27635fbcd5b0e944bfdc63644f0713938a7f51535c3a35e2",
+            hex_to_string(&hmac::<Sha256>(&[0xaa; 131], message)),
+        );
+    }
+
+    #[test]
+    fn test_hmac_md4_matches_definition() {
+        let key = b"Jefe";
+        let message = b"what do ya want for nothing?";
+        let mut block_key = key.to_vec();
+        block_key.resize(md4::BLOCK_SIZE, 0);
+
+        let inner_key = block_key.iter().map(|byte| byte ^ IPAD).collect::<Vec<u8>>();
+        let outer_key = block_key.iter().map(|byte| byte ^ OPAD).collect::<Vec<u8>>();
+        let inner = md4::digest(&[&inner_key[..], message].concat());
+        let expected = md4::digest(&[&outer_key[..], &inner].concat());
+
+        assert_eq!(expected.to_vec(), hmac::<Md4>(key, message));
+    }
+
+    #[test]
+    fn test_update_in_pieces() {
+        let message = b"Test Using Larger Than Block-Size Key and Larger Than One Block-Size Data";
+        let mut hmac = Hmac::<Sha1>::new(&[0xaa; 80]);
+
+        for piece in message.chunks(7) {
+            hmac.update(piece);
+        }
+
+        assert_eq!(super::hmac::<Sha1>(&[0xaa; 80], message), hmac.finalize());
+    }
+}
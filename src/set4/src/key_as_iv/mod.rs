@@ -0,0 +1,132 @@
+use rand::Rng;
+use set1::aes::{self, AesError, BLOCK_SIZE, Mode};
+use set1::xor::xor;
+use set2::bitflipping::{comment_for, is_admin};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum KeyAsIvError {
+    #[error(transparent)]
+    Aes(#[from] AesError),
+
+    #[error("plaintext contains non-ASCII bytes: {plaintext:?}")]
+    NonAscii {
+        plaintext: Vec<u8>,
+    },
+}
+
+// the CBC comment service, except that it saves on ivs by reusing the key, and complains
+// about non-ASCII plaintext by echoing it back
+pub struct KeyAsIvCommentService {
+    key: [u8; BLOCK_SIZE],
+}
+
+impl KeyAsIvCommentService {
+    pub fn new() -> Self {
+        KeyAsIvCommentService { key: rand::rng().random() }
+    }
+
+    pub fn encrypt_userdata(&self, userdata: &str) -> Vec<u8> {
+        aes::encrypt(comment_for(userdata).as_bytes(), &self.key, Mode::CBC { iv: self.key }).unwrap()
+    }
+
+    pub fn is_admin(&self, ciphertext: &[u8]) -> Result<bool, KeyAsIvError> {
+        let plaintext = aes::decrypt(ciphertext, &self.key, Mode::CBC { iv: self.key })?;
+
+        if !plaintext.is_ascii() {
+            return Err(KeyAsIvError::NonAscii { plaintext });
+        }
+
+        Ok(is_admin(&plaintext))
+    }
+}
+
+impl Default for KeyAsIvCommentService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// with the ciphertext turned into C1, 0, C1 the first plaintext block is D(C1) ^ key and the third
+// is D(C1) ^ 0, so xoring them gives the key - the original last two blocks go after them so the
+// padding stays valid, which works for any ciphertext of two or more blocks
+pub fn recover_key(ciphertext: &[u8], oracle: impl Fn(&[u8]) -> Result<bool, KeyAsIvError>) -> Option<[u8; BLOCK_SIZE]> {
+    if ciphertext.len() < 2 * BLOCK_SIZE {
+        return None;
+    }
+
+    let first_block = &ciphertext[..BLOCK_SIZE];
+    let mut modified_ciphertext = first_block.to_vec();
+    modified_ciphertext.extend_from_slice(&[0u8; BLOCK_SIZE]);
+    modified_ciphertext.extend_from_slice(first_block);
+    modified_ciphertext.extend_from_slice(&ciphertext[ciphertext.len() - 2 * BLOCK_SIZE..]);
+
+    let Err(KeyAsIvError::NonAscii { plaintext }) = oracle(&modified_ciphertext) else {
+        return None;
+    };
+
+    xor(&plaintext[..BLOCK_SIZE], &plaintext[2 * BLOCK_SIZE..3 * BLOCK_SIZE]).try_into().ok()
+}
+
+#[cfg(test)]
+mod test_key_as_iv {
+    use super::*;
+
+    #[test]
+    fn test_service_rejects_non_ascii_plaintext() {
+        let key = [1u8; BLOCK_SIZE];
+        let service = KeyAsIvCommentService { key };
+        let ciphertext = aes::encrypt(b"caf\xc3\xa9", &key, Mode::CBC { iv: key }).unwrap();
+
+        let result = service.is_admin(&ciphertext);
+
+        assert_eq!(Err(KeyAsIvError::NonAscii { plaintext: b"caf\xc3\xa9".to_vec() }), result);
+    }
+
+    #[test]
+    fn test_service_not_admin_for_injected_userdata() {
+        let service = KeyAsIvCommentService::new();
+
+        let ciphertext = service.encrypt_userdata(";admin=true;");
+
+        assert_eq!(Ok(false), service.is_admin(&ciphertext));
+    }
+
+    #[test]
+    fn test_recover_key() {
+        let service = KeyAsIvCommentService::new();
+        let ciphertext = service.encrypt_userdata("foo");
+
+        let key = recover_key(&ciphertext, |ciphertext| service.is_admin(ciphertext)).unwrap();
+
+        let fresh_ciphertext = service.encrypt_userdata("bar");
+        let plaintext = aes::decrypt(&fresh_ciphertext, &key, Mode::CBC { iv: key }).unwrap();
+
+        assert_eq!(service.key, key);
+        assert_eq!(comment_for("bar").as_bytes(), plaintext);
+    }
+
+    #[test]
+    fn test_recover_key_few_blocks() {
+        let key = [1u8; BLOCK_SIZE];
+        let service = KeyAsIvCommentService { key };
+
+        for block_count in 2..=4 {
+            let plaintext = vec![b'A'; block_count * BLOCK_SIZE - 1];
+            let ciphertext = aes::encrypt(&plaintext, &key, Mode::CBC { iv: key }).unwrap();
+
+            let recovered_key = recover_key(&ciphertext, |ciphertext| service.is_admin(ciphertext));
+
+            assert_eq!(Some(key), recovered_key);
+        }
+    }
+
+    #[test]
+    fn test_recover_key_short_ciphertext() {
+        let service = KeyAsIvCommentService::new();
+
+        let key = recover_key(&[0u8; BLOCK_SIZE], |ciphertext| service.is_admin(ciphertext));
+
+        assert_eq!(None, key);
+    }
+}
@@ -0,0 +1,150 @@
+use std::marker::PhantomData;
+
+use rand::Rng;
+
+use crate::hash::{Hash, LengthExtension};
+
+pub const MAX_KEY_LEN: usize = 64;
+
+#[derive(Debug, PartialEq)]
+pub struct ForgedMac {
+    pub message: Vec<u8>,
+    pub mac: Vec<u8>,
+    pub key_len: usize,
+}
+
+// the naive MAC - the key is simply hashed in front of the message
+pub fn keyed_mac<H: Hash>(key: &[u8], message: &[u8]) -> Vec<u8> {
+    let mut hash = H::new();
+    hash.update(key);
+    hash.update(message);
+
+    hash.finalize()
+}
+
+// signs and verifies messages with a random key of random length only the service knows
+pub struct KeyedMacService<H: Hash> {
+    key: Vec<u8>,
+    hash: PhantomData<H>,
+}
+
+impl<H: Hash> KeyedMacService<H> {
+    pub fn new() -> Self {
+        let mut rng = rand::rng();
+        let key_len = rng.random_range(1..=MAX_KEY_LEN);
+
+        KeyedMacService { key: (0..key_len).map(|_| rng.random()).collect(), hash: PhantomData }
+    }
+
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        keyed_mac::<H>(&self.key, message)
+    }
+
+    pub fn verify(&self, message: &[u8], mac: &[u8]) -> bool {
+        self.sign(message) == mac
+    }
+}
+
+impl<H: Hash> Default for KeyedMacService<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// the MAC is the hash state after key || message || padding, so hashing can go on from it over
+// an extension - the key length is only needed to get the glue padding and the length right
+pub fn forge_mac<H: LengthExtension>(
+    message: &[u8],
+    mac: &[u8],
+    extension: &[u8],
+    verifier: impl Fn(&[u8], &[u8]) -> bool,
+) -> Option<ForgedMac> {
+    if mac.len() != H::DIGEST_SIZE {
+        return None;
+    }
+
+    (0..=MAX_KEY_LEN).find_map(|key_len| {
+        let glue_padding = H::padding((key_len + message.len()) as u64);
+        let processed_len = key_len + message.len() + glue_padding.len();
+
+        let mut hash = H::from_digest(mac, processed_len as u64);
+        hash.update(extension);
+        let forged_mac = hash.finalize();
+
+        let forged_message = [message, &glue_padding, extension].concat();
+
+        verifier(&forged_message, &forged_mac)
+            .then_some(ForgedMac { message: forged_message, mac: forged_mac, key_len })
+    })
+}
+
+#[cfg(test)]
+mod test_length_extension {
+    use set2::bitflipping::{comment_for, is_admin};
+
+    use crate::md4::Md4;
+    use crate::sha1::Sha1;
+    use crate::sha256::Sha256;
+
+    use super::*;
+
+    fn assert_forges_admin<H: LengthExtension>() {
+        let service = KeyedMacService::<H>::new();
+        let message = comment_for("foo");
+        let mac = service.sign(message.as_bytes());
+
+        let forged = forge_mac::<H>(message.as_bytes(), &mac, b";admin=true", |message, mac| service.verify(message, mac)).unwrap();
+
+        assert_eq!(service.key.len(), forged.key_len);
+        assert!(forged.message.ends_with(b";admin=true"));
+        assert!(is_admin(&forged.message));
+        assert!(service.verify(&forged.message, &forged.mac));
+    }
+
+    fn assert_mac_depends_on_key_and_message<H: Hash>() {
+        let mac = keyed_mac::<H>(b"key", b"message");
+
+        assert_eq!(H::digest(b"keymessage"), mac);
+        assert_ne!(mac, keyed_mac::<H>(b"other key", b"message"));
+        assert_ne!(mac, keyed_mac::<H>(b"key", b"other message"));
+    }
+
+    #[test]
+    fn test_keyed_mac_depends_on_key_and_message() {
+        assert_mac_depends_on_key_and_message::<Sha1>();
+        assert_mac_depends_on_key_and_message::<Md4>();
+    }
+
+    #[test]
+    fn test_service_rejects_tampered_message() {
+        let service = KeyedMacService::<Sha1>::new();
+        let mac = service.sign(b"message");
+
+        assert!(service.verify(b"message", &mac));
+        assert!(!service.verify(b"messagf", &mac));
+    }
+
+    #[test]
+    fn test_forge_mac_sha1() {
+        assert_forges_admin::<Sha1>();
+    }
+
+    #[test]
+    fn test_forge_mac_md4() {
+        assert_forges_admin::<Md4>();
+    }
+
+    #[test]
+    fn test_forge_mac_sha256() {
+        assert_forges_admin::<Sha256>();
+    }
+
+    #[test]
+    fn test_forge_mac_wrong_mac_size() {
+        let service = KeyedMacService::<Md4>::new();
+
+        let forged = forge_mac::<Md4>(b"message", &[0u8; 20], b"extension", |message, mac| service.verify(message, mac));
+
+        assert_eq!(None, forged);
+    }
+}
@@ -0,0 +1,12 @@
+pub mod ctr_bitflipping;
+pub mod ctr_edit;
+pub mod hash;
+pub mod hmac;
+pub mod key_as_iv;
+pub mod length_extension;
+pub mod md4;
+pub mod md4_mac;
+pub mod merkle_damgard;
+pub mod sha1;
+pub mod sha1_mac;
+pub mod sha256;
@@ -0,0 +1,56 @@
+use set1::{aes, base64, util};
+use set2::bitflipping;
+use set4::{ctr_bitflipping, ctr_edit, key_as_iv, md4_mac, sha1_mac};
+
+fn main() {
+    // Challenge 1 - break "random access read/write" AES CTR
+    let base64_input = std::fs::read_to_string("input/25.txt").unwrap().replace("\n", "");
+    let input = base64::decode(base64_input.as_bytes()).unwrap();
+    let plaintext = aes::decrypt(&input, b"YELLOW SUBMARINE", aes::Mode::ECB).unwrap();
+    let service = ctr_edit::CtrEditService::new();
+    let ciphertext = service.encrypt(&plaintext);
+    let edit = |ciphertext: &[u8], offset: usize, newtext: &[u8]| service.edit(ciphertext, offset, newtext);
+    let recovered = ctr_edit::recover_plaintext(&ciphertext, edit).unwrap();
+
+    println!("{}", String::from_utf8_lossy(&recovered));
+
+    // Challenge 2 - CTR bitflipping
+    let service = ctr_bitflipping::CtrCommentService::new();
+    let forged_ciphertext = ctr_bitflipping::forge_admin(|userdata| service.encrypt_userdata(userdata));
+
+    println!("admin: {}", service.is_admin(&forged_ciphertext).unwrap());
+
+    // Challenge 3 - recover the key from CBC with IV=Key
+    let service = key_as_iv::KeyAsIvCommentService::new();
+    let ciphertext = service.encrypt_userdata("foo");
+    let key = key_as_iv::recover_key(&ciphertext, |ciphertext| service.is_admin(ciphertext)).unwrap();
+    let fresh_ciphertext = service.encrypt_userdata("bar");
+    let plaintext = aes::decrypt(&fresh_ciphertext, &key, aes::Mode::CBC { iv: key }).unwrap();
+
+    println!("key: {:?}", key);
+    println!("{}", String::from_utf8_lossy(&plaintext));
+
+    // Challenge 4 - implement a SHA-1 keyed MAC
+    let mac = sha1_mac::sha1_keyed_mac(b"YELLOW SUBMARINE", b"message");
+
+    println!("{}", util::hex_to_string(&mac));
+
+    // Challenge 5 - break a SHA-1 keyed MAC using length extension
+    let service = sha1_mac::Sha1MacService::new();
+    let message = bitflipping::comment_for("foo");
+    let mac = service.sign(message.as_bytes());
+    let verifier = |message: &[u8], mac: &[u8]| service.verify(message, mac);
+    let forged = sha1_mac::forge_sha1_mac(message.as_bytes(), &mac, b";admin=true", verifier).unwrap();
+
+    println!("key length: {}, valid: {}", forged.key_len, service.verify(&forged.message, &forged.mac));
+    println!("{}", String::from_utf8_lossy(&forged.message));
+
+    // Challenge 6 - break an MD4 keyed MAC using length extension
+    let service = md4_mac::Md4MacService::new();
+    let mac = service.sign(message.as_bytes());
+    let verifier = |message: &[u8], mac: &[u8]| service.verify(message, mac);
+    let forged = md4_mac::forge_md4_mac(message.as_bytes(), &mac, b";admin=true", verifier).unwrap();
+
+    println!("key length: {}, valid: {}", forged.key_len, service.verify(&forged.message, &forged.mac));
+    println!("{}", String::from_utf8_lossy(&forged.message));
+}
@@ -0,0 +1,184 @@
+use crate::hash::{Hash, LengthExtension};
+use crate::merkle_damgard::{self, ByteOrder, MerkleDamgard};
+
+pub const BLOCK_SIZE: usize = merkle_damgard::BLOCK_SIZE;
+pub const DIGEST_SIZE: usize = 16;
+
+pub const INITIAL_STATE: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+
+// the order the block words are used in by the second and third round
+const ROUND_2_WORDS: [usize; 16] = [0, 4, 8, 12, 1, 5, 9, 13, 2, 6, 10, 14, 3, 7, 11, 15];
+const ROUND_3_WORDS: [usize; 16] = [0, 8, 4, 12, 2, 10, 6, 14, 1, 9, 5, 13, 3, 11, 7, 15];
+
+// MD4 as specified in RFC 1320
+pub struct Md4(MerkleDamgard<[u32; 4]>);
+
+impl Md4 {
+    pub fn new() -> Self {
+        Self::from_state(INITIAL_STATE, 0)
+    }
+
+    // MD4 is little-endian throughout, the length in the padding included
+    pub fn from_state(state: [u32; 4], processed_len: u64) -> Self {
+        Md4(MerkleDamgard::new(state, processed_len, compress, ByteOrder::LittleEndian))
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.0.update(input);
+    }
+
+    pub fn finalize(self) -> [u8; DIGEST_SIZE] {
+        let mut digest = [0u8; DIGEST_SIZE];
+
+        for (chunk, register) in digest.chunks_exact_mut(4).zip(self.0.finalize()) {
+            chunk.copy_from_slice(&register.to_le_bytes());
+        }
+
+        digest
+    }
+}
+
+impl Default for Md4 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hash for Md4 {
+    const BLOCK_SIZE: usize = BLOCK_SIZE;
+    const DIGEST_SIZE: usize = DIGEST_SIZE;
+
+    fn new() -> Self {
+        Md4::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Md4::update(self, input)
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        Md4::finalize(self).to_vec()
+    }
+}
+
+impl LengthExtension for Md4 {
+    fn padding(message_len: u64) -> Vec<u8> {
+        merkle_damgard::padding(message_len, ByteOrder::LittleEndian)
+    }
+
+    fn from_digest(digest: &[u8], processed_len: u64) -> Self {
+        let mut state = [0u32; 4];
+
+        for (register, chunk) in state.iter_mut().zip(digest.chunks_exact(4)) {
+            *register = u32::from_le_bytes(chunk.try_into().unwrap());
+        }
+
+        Md4::from_state(state, processed_len)
+    }
+}
+
+pub fn digest(input: &[u8]) -> [u8; DIGEST_SIZE] {
+    let mut md4 = Md4::new();
+    md4.update(input);
+
+    md4.finalize()
+}
+
+fn f(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (!x & z)
+}
+
+fn g(x: u32, y: u32, z: u32) -> u32 {
+    (x & y) | (x & z) | (y & z)
+}
+
+fn h(x: u32, y: u32, z: u32) -> u32 {
+    x ^ y ^ z
+}
+
+// every round has 16 steps, each updating one register from the other three - the updated
+// register moves backwards through a, d, c, b
+fn round(
+    registers: &mut [u32; 4],
+    function: fn(u32, u32, u32) -> u32,
+    words: [u32; 16],
+    constant: u32,
+    shifts: [u32; 4],
+) {
+    for (i, word) in words.iter().enumerate() {
+        let target = (4 - i % 4) % 4;
+        let x = registers[(target + 1) % 4];
+        let y = registers[(target + 2) % 4];
+        let z = registers[(target + 3) % 4];
+
+        registers[target] = registers[target]
+            .wrapping_add(function(x, y, z))
+            .wrapping_add(*word)
+            .wrapping_add(constant)
+            .rotate_left(shifts[i % 4]);
+    }
+}
+
+fn compress(state: &mut [u32; 4], block: &[u8; BLOCK_SIZE]) {
+    let mut x = [0u32; 16];
+
+    for (i, word) in block.chunks_exact(4).enumerate() {
+        x[i] = u32::from_le_bytes(word.try_into().unwrap());
+    }
+
+    let mut registers = *state;
+    round(&mut registers, f, x, 0, [3, 7, 11, 19]);
+    round(&mut registers, g, ROUND_2_WORDS.map(|i| x[i]), 0x5a827999, [3, 5, 9, 13]);
+    round(&mut registers, h, ROUND_3_WORDS.map(|i| x[i]), 0x6ed9eba1, [3, 9, 11, 15]);
+
+    for (register, value) in state.iter_mut().zip(registers) {
+        *register = register.wrapping_add(value);
+    }
+}
+
+#[cfg(test)]
+mod test_md4 {
+    use set1::util::hex_to_string;
+
+    use super::*;
+
+    #[test]
+    fn test_rfc_1320_vectors() {
+        let vectors = [
+            ("", "31d6cfe0d16ae931b73c59d7e0c089c0"),
+            ("a", "bde52cb31de33e46245e05fbdbd6fb24"),
+            ("abc", "a448017aaf21d8525fc10ae87aa6729d"),
+            ("message digest", "d9130a8164549fe818874806e1c7014b"),
+            ("abcdefghijklmnopqrstuvwxyz", "d79e1c308aa5bbcdeea8ed63df412da9"),
+            ("ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789", "043f8582f241db351ce627e153e7f0e4"),
+            ("12345678901234567890123456789012345678901234567890123456789012345678901234567890", "e33b4ddc9c38f2199c3e7b164fcc0536"),
+        ];
+
+        for (input, expected) in vectors {
+            assert_eq!(expected, hex_to_string(&digest(input.as_bytes())));
+        }
+    }
+
+    #[test]
+    fn test_update_in_pieces() {
+        let input = b"12345678901234567890123456789012345678901234567890123456789012345678901234567890";
+        let mut md4 = Md4::new();
+
+        for piece in input.chunks(7) {
+            md4.update(piece);
+        }
+
+        assert_eq!(digest(input), md4.finalize());
+    }
+
+    #[test]
+    fn test_from_digest_continues_hash() {
+        let first_block = [b'x'; BLOCK_SIZE - 9];
+        let glue_padding = Md4::padding(first_block.len() as u64);
+
+        let mut md4 = Md4::from_digest(&digest(&first_block), BLOCK_SIZE as u64);
+        md4.update(b"abc");
+
+        assert_eq!(digest(&[&first_block[..], &glue_padding, b"abc"].concat()), md4.finalize());
+    }
+}
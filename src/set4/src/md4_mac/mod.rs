@@ -0,0 +1,17 @@
+use crate::length_extension::{self, ForgedMac, KeyedMacService, keyed_mac};
+use crate::md4::Md4;
+
+pub type Md4MacService = KeyedMacService<Md4>;
+
+pub fn md4_keyed_mac(key: &[u8], message: &[u8]) -> Vec<u8> {
+    keyed_mac::<Md4>(key, message)
+}
+
+pub fn forge_md4_mac(
+    message: &[u8],
+    mac: &[u8],
+    extension: &[u8],
+    verifier: impl Fn(&[u8], &[u8]) -> bool,
+) -> Option<ForgedMac> {
+    length_extension::forge_mac::<Md4>(message, mac, extension, verifier)
+}
@@ -0,0 +1,124 @@
+pub const BLOCK_SIZE: usize = 64;
+
+// the byte order of the message length in the padding
+#[derive(Clone, Copy)]
+pub enum ByteOrder {
+    BigEndian,
+    LittleEndian,
+}
+
+// the buffering and padding shared by MD4, SHA-1 and SHA-256 - only the state and the compression
+// of a block into it differ between them
+pub struct MerkleDamgard<S> {
+    state: S,
+    compress: fn(&mut S, &[u8; BLOCK_SIZE]),
+    length_order: ByteOrder,
+    buffer: Vec<u8>,
+    length: u64,
+}
+
+impl<S> MerkleDamgard<S> {
+    // starts from the given state as if `processed_len` bytes (padding included) had already gone
+    // through it - the length only matters for the final padding
+    pub fn new(state: S, processed_len: u64, compress: fn(&mut S, &[u8; BLOCK_SIZE]), length_order: ByteOrder) -> Self {
+        MerkleDamgard { state, compress, length_order, buffer: Vec::with_capacity(BLOCK_SIZE), length: processed_len }
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.length += input.len() as u64;
+        self.buffer.extend_from_slice(input);
+
+        let full_blocks_len = self.buffer.len() - self.buffer.len() % BLOCK_SIZE;
+
+        for block in self.buffer[..full_blocks_len].chunks_exact(BLOCK_SIZE) {
+            (self.compress)(&mut self.state, block.try_into().unwrap());
+        }
+
+        self.buffer.drain(..full_blocks_len);
+    }
+
+    // returns the state after the padding, which the hash encodes as its digest
+    pub fn finalize(mut self) -> S {
+        let padding = padding(self.length, self.length_order);
+        self.update(&padding);
+
+        self.state
+    }
+}
+
+// a one bit, zeros up to 8 bytes short of a block boundary, then the message length in bits as
+// a 64-bit number
+pub fn padding(message_len: u64, length_order: ByteOrder) -> Vec<u8> {
+    let zeros_len = (BLOCK_SIZE - (message_len as usize + 9) % BLOCK_SIZE) % BLOCK_SIZE;
+    let mut padding = vec![0x80];
+    padding.resize(1 + zeros_len, 0);
+
+    let bit_len = message_len.wrapping_mul(8);
+    match length_order {
+        ByteOrder::BigEndian => padding.extend_from_slice(&bit_len.to_be_bytes()),
+        ByteOrder::LittleEndian => padding.extend_from_slice(&bit_len.to_le_bytes()),
+    }
+
+    padding
+}
+
+#[cfg(test)]
+mod test_merkle_damgard {
+    use super::*;
+
+    // sums up the blocks, so the final state tells how many bytes were compressed
+    fn compress_sum(state: &mut u64, block: &[u8; BLOCK_SIZE]) {
+        *state += block.iter().map(|byte| *byte as u64).sum::<u64>();
+    }
+
+    #[test]
+    fn test_padding_length() {
+        for message_len in 0..200 {
+            let padding = padding(message_len, ByteOrder::BigEndian);
+
+            assert!((message_len as usize + padding.len()).is_multiple_of(BLOCK_SIZE));
+            assert!(padding.len() > 8 && padding.len() <= BLOCK_SIZE + 8);
+        }
+    }
+
+    #[test]
+    fn test_padding_big_endian() {
+        let mut expected = vec![0x80];
+        expected.resize(BLOCK_SIZE - 3 - 8, 0);
+        expected.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 24]);
+
+        assert_eq!(expected, padding(3, ByteOrder::BigEndian));
+    }
+
+    #[test]
+    fn test_padding_little_endian() {
+        let mut expected = vec![0x80];
+        expected.resize(BLOCK_SIZE - 3 - 8, 0);
+        expected.extend_from_slice(&[24, 0, 0, 0, 0, 0, 0, 0]);
+
+        assert_eq!(expected, padding(3, ByteOrder::LittleEndian));
+    }
+
+    #[test]
+    fn test_update_in_pieces() {
+        let input = [1u8; 100];
+        let mut whole = MerkleDamgard::new(0, 0, compress_sum, ByteOrder::BigEndian);
+        let mut pieces = MerkleDamgard::new(0, 0, compress_sum, ByteOrder::BigEndian);
+
+        whole.update(&input);
+        for piece in input.chunks(7) {
+            pieces.update(piece);
+        }
+
+        assert_eq!(whole.finalize(), pieces.finalize());
+    }
+
+    #[test]
+    fn test_finalize_compresses_padding() {
+        let mut hash = MerkleDamgard::new(0, 0, compress_sum, ByteOrder::BigEndian);
+        hash.update(&[1u8; 3]);
+
+        // three ones, the 0x80 bit and the 24 bit length
+        assert_eq!(3 + 0x80 + 24, hash.finalize());
+    }
+}
@@ -0,0 +1,191 @@
+use crate::hash::{Hash, LengthExtension};
+use crate::merkle_damgard::{self, ByteOrder, MerkleDamgard};
+
+pub const BLOCK_SIZE: usize = merkle_damgard::BLOCK_SIZE;
+pub const DIGEST_SIZE: usize = 20;
+
+pub const INITIAL_STATE: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+
+// SHA-1 as specified in FIPS 180-4
+pub struct Sha1(MerkleDamgard<[u32; 5]>);
+
+impl Sha1 {
+    pub fn new() -> Self {
+        Self::from_state(INITIAL_STATE, 0)
+    }
+
+    pub fn from_state(state: [u32; 5], processed_len: u64) -> Self {
+        Sha1(MerkleDamgard::new(state, processed_len, compress, ByteOrder::BigEndian))
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.0.update(input);
+    }
+
+    pub fn finalize(self) -> [u8; DIGEST_SIZE] {
+        let mut digest = [0u8; DIGEST_SIZE];
+
+        for (chunk, register) in digest.chunks_exact_mut(4).zip(self.0.finalize()) {
+            chunk.copy_from_slice(&register.to_be_bytes());
+        }
+
+        digest
+    }
+}
+
+impl Default for Sha1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hash for Sha1 {
+    const BLOCK_SIZE: usize = BLOCK_SIZE;
+    const DIGEST_SIZE: usize = DIGEST_SIZE;
+
+    fn new() -> Self {
+        Sha1::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Sha1::update(self, input)
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        Sha1::finalize(self).to_vec()
+    }
+}
+
+impl LengthExtension for Sha1 {
+    fn padding(message_len: u64) -> Vec<u8> {
+        merkle_damgard::padding(message_len, ByteOrder::BigEndian)
+    }
+
+    fn from_digest(digest: &[u8], processed_len: u64) -> Self {
+        let mut state = [0u32; 5];
+
+        for (register, chunk) in state.iter_mut().zip(digest.chunks_exact(4)) {
+            *register = u32::from_be_bytes(chunk.try_into().unwrap());
+        }
+
+        Sha1::from_state(state, processed_len)
+    }
+}
+
+pub fn digest(input: &[u8]) -> [u8; DIGEST_SIZE] {
+    let mut sha1 = Sha1::new();
+    sha1.update(input);
+
+    sha1.finalize()
+}
+
+// extends the 16 block words into the 80 words used by the rounds
+fn message_schedule(block: &[u8; BLOCK_SIZE]) -> [u32; 80] {
+    let mut w = [0u32; 80];
+
+    for (i, word) in block.chunks_exact(4).enumerate() {
+        w[i] = u32::from_be_bytes(word.try_into().unwrap());
+    }
+
+    for i in 16..80 {
+        w[i] = (w[i - 3] ^ w[i - 8] ^ w[i - 14] ^ w[i - 16]).rotate_left(1);
+    }
+
+    w
+}
+
+fn compress(state: &mut [u32; 5], block: &[u8; BLOCK_SIZE]) {
+    let w = message_schedule(block);
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+
+    for (i, word) in w.iter().enumerate() {
+        let (f, k) = match i {
+            0..20 => ((b & c) | (!b & d), 0x5a827999),
+            20..40 => (b ^ c ^ d, 0x6ed9eba1),
+            40..60 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6),
+        };
+
+        let temp = a.rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(*word);
+
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    for (register, value) in state.iter_mut().zip([a, b, c, d, e]) {
+        *register = register.wrapping_add(value);
+    }
+}
+
+#[cfg(test)]
+mod test_sha1 {
+    use set1::util::hex_to_string;
+
+    use super::*;
+
+    #[test]
+    fn test_digest_empty() {
+        assert_eq!("da39a3ee5e6b4b0d3255bfef95601890afd80709", hex_to_string(&digest(b"")));
+    }
+
+    #[test]
+    fn test_digest_abc() {
+        assert_eq!("a9993e364706816aba3e25717850c26c9cd0d89d", hex_to_string(&digest(b"abc")));
+    }
+
+    #[test]
+    fn test_digest_two_blocks() {
+        let input = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+
+        assert_eq!("84983e441c3bd26ebaae4aa1f95129e5e54670f1", hex_to_string(&digest(input)));
+    }
+
+    #[test]
+    fn test_digest_million_a() {
+        let input = vec![b'a'; 1_000_000];
+
+        assert_eq!("34aa973cd4c4daa4f61eeb2bdbad27316534016f", hex_to_string(&digest(&input)));
+    }
+
+    #[test]
+    fn test_update_in_pieces() {
+        let input = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+        let mut sha1 = Sha1::new();
+
+        for piece in input.chunks(7) {
+            sha1.update(piece);
+        }
+
+        assert_eq!(digest(input), sha1.finalize());
+    }
+
+    #[test]
+    fn test_from_state_continues_hash() {
+        let first_block = [b'x'; BLOCK_SIZE];
+        let mut state = INITIAL_STATE;
+        compress(&mut state, &first_block);
+
+        let mut sha1 = Sha1::from_state(state, BLOCK_SIZE as u64);
+        sha1.update(b"abc");
+
+        assert_eq!(digest(&[&first_block[..], b"abc"].concat()), sha1.finalize());
+    }
+
+    #[test]
+    fn test_from_digest_continues_hash() {
+        let first_block = [b'x'; BLOCK_SIZE - 9];
+        let glue_padding = Sha1::padding(first_block.len() as u64);
+
+        let mut sha1 = Sha1::from_digest(&digest(&first_block), BLOCK_SIZE as u64);
+        sha1.update(b"abc");
+
+        assert_eq!(digest(&[&first_block[..], &glue_padding, b"abc"].concat()), sha1.finalize());
+    }
+}
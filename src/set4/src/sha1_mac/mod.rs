@@ -0,0 +1,17 @@
+use crate::length_extension::{self, ForgedMac, KeyedMacService, keyed_mac};
+use crate::sha1::Sha1;
+
+pub type Sha1MacService = KeyedMacService<Sha1>;
+
+pub fn sha1_keyed_mac(key: &[u8], message: &[u8]) -> Vec<u8> {
+    keyed_mac::<Sha1>(key, message)
+}
+
+pub fn forge_sha1_mac(
+    message: &[u8],
+    mac: &[u8],
+    extension: &[u8],
+    verifier: impl Fn(&[u8], &[u8]) -> bool,
+) -> Option<ForgedMac> {
+    length_extension::forge_mac::<Sha1>(message, mac, extension, verifier)
+}
@@ -0,0 +1,193 @@
+use crate::hash::{Hash, LengthExtension};
+use crate::merkle_damgard::{self, ByteOrder, MerkleDamgard};
+
+pub const BLOCK_SIZE: usize = merkle_damgard::BLOCK_SIZE;
+pub const DIGEST_SIZE: usize = 32;
+
+pub const INITIAL_STATE: [u32; 8] = [
+    0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab, 0x5be0cd19,
+];
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+// SHA-256 from FIPS 180-4 - built like SHA-1, but with eight registers and 64 rounds
+pub struct Sha256(MerkleDamgard<[u32; 8]>);
+
+impl Sha256 {
+    pub fn new() -> Self {
+        Self::from_state(INITIAL_STATE, 0)
+    }
+
+    pub fn from_state(state: [u32; 8], processed_len: u64) -> Self {
+        Sha256(MerkleDamgard::new(state, processed_len, compress, ByteOrder::BigEndian))
+    }
+
+    pub fn update(&mut self, input: &[u8]) {
+        self.0.update(input);
+    }
+
+    pub fn finalize(self) -> [u8; DIGEST_SIZE] {
+        let mut digest = [0u8; DIGEST_SIZE];
+
+        for (chunk, register) in digest.chunks_exact_mut(4).zip(self.0.finalize()) {
+            chunk.copy_from_slice(&register.to_be_bytes());
+        }
+
+        digest
+    }
+}
+
+impl Default for Sha256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Hash for Sha256 {
+    const BLOCK_SIZE: usize = BLOCK_SIZE;
+    const DIGEST_SIZE: usize = DIGEST_SIZE;
+
+    fn new() -> Self {
+        Sha256::new()
+    }
+
+    fn update(&mut self, input: &[u8]) {
+        Sha256::update(self, input)
+    }
+
+    fn finalize(self) -> Vec<u8> {
+        Sha256::finalize(self).to_vec()
+    }
+}
+
+impl LengthExtension for Sha256 {
+    fn padding(message_len: u64) -> Vec<u8> {
+        merkle_damgard::padding(message_len, ByteOrder::BigEndian)
+    }
+
+    fn from_digest(digest: &[u8], processed_len: u64) -> Self {
+        let mut state = [0u32; 8];
+
+        for (register, chunk) in state.iter_mut().zip(digest.chunks_exact(4)) {
+            *register = u32::from_be_bytes(chunk.try_into().unwrap());
+        }
+
+        Sha256::from_state(state, processed_len)
+    }
+}
+
+pub fn digest(input: &[u8]) -> [u8; DIGEST_SIZE] {
+    let mut sha256 = Sha256::new();
+    sha256.update(input);
+
+    sha256.finalize()
+}
+
+// extends the 16 block words into the 64 words used by the rounds
+fn message_schedule(block: &[u8; BLOCK_SIZE]) -> [u32; 64] {
+    let mut w = [0u32; 64];
+
+    for (i, word) in block.chunks_exact(4).enumerate() {
+        w[i] = u32::from_be_bytes(word.try_into().unwrap());
+    }
+
+    for i in 16..64 {
+        let s0 = w[i - 15].rotate_right(7) ^ w[i - 15].rotate_right(18) ^ (w[i - 15] >> 3);
+        let s1 = w[i - 2].rotate_right(17) ^ w[i - 2].rotate_right(19) ^ (w[i - 2] >> 10);
+
+        w[i] = w[i - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[i - 7])
+            .wrapping_add(s1);
+    }
+
+    w
+}
+
+fn compress(state: &mut [u32; 8], block: &[u8; BLOCK_SIZE]) {
+    let w = message_schedule(block);
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+
+    for (word, k) in w.iter().zip(K) {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let temp1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(k)
+            .wrapping_add(*word);
+
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(maj);
+
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+
+    for (register, value) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *register = register.wrapping_add(value);
+    }
+}
+
+#[cfg(test)]
+mod test_sha256 {
+    use set1::util::hex_to_string;
+
+    use super::*;
+
+    #[test]
+    fn test_digest_empty() {
+        assert_eq!("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855", hex_to_string(&digest(b"")));
+    }
+
+    #[test]
+    fn test_digest_abc() {
+        assert_eq!("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad", hex_to_string(&digest(b"abc")));
+    }
+
+    #[test]
+    fn test_digest_two_blocks() {
+        let input = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+
+        assert_eq!("248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1", hex_to_string(&digest(input)));
+    }
+
+    #[test]
+    fn test_update_in_pieces() {
+        let input = b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq";
+        let mut sha256 = Sha256::new();
+
+        for piece in input.chunks(7) {
+            sha256.update(piece);
+        }
+
+        assert_eq!(digest(input), sha256.finalize());
+    }
+
+    #[test]
+    fn test_from_digest_continues_hash() {
+        let first_block = [b'x'; BLOCK_SIZE - 9];
+        let glue_padding = Sha256::padding(first_block.len() as u64);
+
+        let mut sha256 = Sha256::from_digest(&digest(&first_block), BLOCK_SIZE as u64);
+        sha256.update(b"abc");
+
+        assert_eq!(digest(&[&first_block[..], &glue_padding, b"abc"].concat()), sha256.finalize());
+    }
+}